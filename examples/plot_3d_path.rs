@@ -0,0 +1,58 @@
+extern crate path_planning as pp;
+use path_planning::env::Obstacle;
+use path_planning::env::Obstacle::{CapsuleObstacle, CircleObstacle, RectObstacle};
+use path_planning::plot::{Figure, PlotBuilder3d};
+
+fn main() {
+    let low = [0.0, 0.0, 0.0];
+    let high = [30.0, 30.0, 20.0];
+    let obstacles: Vec<Obstacle<3>> = vec![
+        RectObstacle {
+            center: [10.0, 10.0, 10.0],
+            size: [4.0, 12.0, 20.0],
+        },
+        RectObstacle {
+            center: [21.0, 18.0, 6.0],
+            size: [10.0, 4.0, 12.0],
+        },
+        CircleObstacle {
+            center: [20.0, 8.0, 12.0],
+            radius: 4.0,
+        },
+        CircleObstacle {
+            center: [8.0, 24.0, 6.0],
+            radius: 3.0,
+        },
+        CapsuleObstacle {
+            start: [16.0, 26.0, 2.0],
+            end: [26.0, 26.0, 16.0],
+            radius: 2.0,
+        },
+    ];
+    let env = pp::env::Env::new(low, high, obstacles);
+
+    let start: [f32; 3] = [2.0, 2.0, 2.0];
+    let goal: [f32; 3] = [28.0, 28.0, 18.0];
+
+    let _env = env.clone();
+    let is_approved = Box::new(move |position: &[f32; 3]| !_env.is_inside_obstacle(position));
+
+    let mut rrtstar =
+        pp::planner::RRTStar::new(start, goal, low, high, is_approved, 0.2, 2.0, 2000);
+    let path = rrtstar.plan();
+
+    PlotBuilder3d::new(&env)
+        .tree(&rrtstar.nodes)
+        .path(&path, "rrt*")
+        .start(start)
+        .goal(goal)
+        .save("path_3d.png", pp::plot::DEFAULT_SIZE)
+        .unwrap();
+
+    let meshes = [
+        pp::plot::create_env_mesh(&env),
+        pp::plot::create_tree_mesh(&rrtstar.nodes),
+        pp::plot::create_path_mesh(&path),
+    ];
+    pp::plot::save_obj("path_3d.obj", &meshes).unwrap();
+}
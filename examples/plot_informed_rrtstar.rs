@@ -1,60 +1,35 @@
 extern crate path_planning as pp;
-use path_planning::env::Obstacle;
-use path_planning::env::Obstacle::CircleObstacle;
-use path_planning::env::Obstacle::RectObstacle;
+use path_planning::plot::{Figure, PlotBuilder};
+use path_planning::scenario::Scenario;
 
 fn main() {
-    let low = [0.0, 0.0];
-    let high = [50.0, 30.0];
-    let obstacles: Vec<Obstacle<2>> = vec![
-        RectObstacle {
-            center: [18.0, 13.0],
-            size: [8.0, 2.0],
-        },
-        RectObstacle {
-            center: [22.0, 23.5],
-            size: [8.0, 3.0],
-        },
-        RectObstacle {
-            center: [27.0, 13.0],
-            size: [2.0, 12.0],
-        },
-        RectObstacle {
-            center: [37.0, 15.0],
-            size: [10.0, 2.0],
-        },
-        CircleObstacle {
-            center: [7.0, 12.0],
-            radius: 3.0,
-        },
-        CircleObstacle {
-            center: [46.0, 20.0],
-            radius: 2.0,
-        },
-        CircleObstacle {
-            center: [15.0, 5.0],
-            radius: 2.0,
-        },
-        CircleObstacle {
-            center: [37.0, 7.0],
-            radius: 3.0,
-        },
-        CircleObstacle {
-            center: [37.0, 23.0],
-            radius: 3.0,
-        },
-    ];
-    let env = pp::env::Env::new(low, high, obstacles);
-
-    let low: [f32; 2] = env.low.clone();
-    let high: [f32; 2] = env.high.clone();
-    let start: [f32; 2] = [1.0, 1.0];
-    let goal: [f32; 2] = [48.0, 25.0];
+    let scenario: Scenario<2> = Scenario::load("examples/scenarios/example_2d.toml").unwrap();
+    let env = scenario.create_env();
+    let query = &scenario.queries[0];
+    let config = &scenario.planner;
 
     let _env = env.clone();
     let is_approved = Box::new(move |position: &[f32; 2]| !_env.is_inside_obstacle(position));
 
-    let mut rrt = pp::planner::InformedRRTStar::new(start, goal, low, high, is_approved, 0.2, 2.0, 2000);
+    let mut rrt = pp::planner::InformedRRTStar::new(
+        query.start,
+        query.goal,
+        scenario.low,
+        scenario.high,
+        is_approved,
+        config.goal_sample_rate,
+        config.step_size,
+        config.max_iter,
+    );
     let path = rrt.plan();
-    pp::plot::plot_path(&env, &path).unwrap();
+
+    let mut plot = PlotBuilder::new(&env)
+        .tree(&rrt.nodes)
+        .path(&path, "informed rrt*")
+        .start(query.start)
+        .goal(query.goal);
+    if let Some(c_best) = rrt.stats.get_best_cost() {
+        plot = plot.informed_ellipse(query.start, query.goal, c_best);
+    }
+    plot.save("path.png", pp::plot::DEFAULT_SIZE).unwrap();
 }
@@ -0,0 +1,9 @@
+mod benchmark;
+mod results;
+pub use crate::benchmark::benchmark::Benchmark;
+pub use crate::benchmark::benchmark::BenchmarkProblem;
+pub use crate::benchmark::benchmark::PlannerFactory;
+pub use crate::benchmark::results::escape_csv_field;
+pub use crate::benchmark::results::BenchmarkResults;
+pub use crate::benchmark::results::BenchmarkSummary;
+pub use crate::benchmark::results::TrialResult;
@@ -0,0 +1,113 @@
+use crate::benchmark::BenchmarkResults;
+use crate::benchmark::TrialResult;
+use crate::env::Env;
+use crate::planner::set_seed;
+use crate::planner::AbstractRRT;
+use std::time::Instant;
+
+/// Start and goal to plan between in an environment.
+pub struct BenchmarkProblem<const D: usize> {
+    pub name: String,
+    pub env: Env<D>,
+    pub start: [f32; D],
+    pub goal: [f32; D],
+}
+
+impl<const D: usize> BenchmarkProblem<D> {
+    pub fn new(name: &str, env: Env<D>, start: [f32; D], goal: [f32; D]) -> Self {
+        BenchmarkProblem {
+            name: name.to_string(),
+            env,
+            start,
+            goal,
+        }
+    }
+}
+
+/// Creates a configured planner for a problem. The iteration budget is given by the `max_iter`
+/// of the created planner.
+pub type PlannerFactory<const D: usize> =
+    Box<dyn Fn(&BenchmarkProblem<D>) -> Box<dyn AbstractRRT<D>>>;
+
+/// Runs every planner on every problem for a number of seeded trials.
+pub struct Benchmark<const D: usize> {
+    problems: Vec<BenchmarkProblem<D>>,
+    planners: Vec<(String, PlannerFactory<D>)>,
+    pub num_trials: usize,
+    /// Trial `k` is run with the seed `seed + k` for every planner.
+    pub seed: u64,
+    /// Time budget of each trial in seconds.
+    pub max_time: Option<f32>,
+}
+
+impl<const D: usize> Benchmark<D> {
+    pub fn new(num_trials: usize, seed: u64) -> Self {
+        Benchmark {
+            problems: Vec::new(),
+            planners: Vec::new(),
+            num_trials,
+            seed,
+            max_time: None,
+        }
+    }
+
+    pub fn add_problem(&mut self, problem: BenchmarkProblem<D>) {
+        self.problems.push(problem);
+    }
+
+    /// Adds a problem for each query of the scenario, named `name` followed by the query index.
+    #[cfg(feature = "serde")]
+    pub fn add_scenario(&mut self, name: &str, scenario: &crate::scenario::Scenario<D>) {
+        let env = scenario.create_env();
+        for (k, query) in scenario.queries.iter().enumerate() {
+            self.add_problem(BenchmarkProblem::new(
+                &format!("{}/{}", name, k),
+                env.clone(),
+                query.start,
+                query.goal,
+            ));
+        }
+    }
+
+    pub fn add_planner(&mut self, name: &str, factory: PlannerFactory<D>) {
+        self.planners.push((name.to_string(), factory));
+    }
+
+    pub fn run(&self) -> BenchmarkResults {
+        let mut results = BenchmarkResults::new();
+        for problem in self.problems.iter() {
+            for (planner_name, factory) in self.planners.iter() {
+                for trial in 0..self.num_trials {
+                    let seed = self.seed + trial as u64;
+                    set_seed(seed);
+
+                    let mut planner = factory(problem);
+                    planner.set_max_time(self.max_time);
+
+                    let start_time = Instant::now();
+                    let path = planner.plan();
+                    let planning_time = start_time.elapsed().as_secs_f32();
+
+                    let stats = planner.get_stats();
+                    let is_solved = path.len() >= 2 && path.last() == Some(&problem.goal);
+                    results.trials.push(TrialResult {
+                        problem: problem.name.clone(),
+                        planner: planner_name.clone(),
+                        trial,
+                        seed,
+                        is_solved,
+                        planning_time,
+                        cost: if is_solved {
+                            stats.get_best_cost()
+                        } else {
+                            None
+                        },
+                        num_nodes: planner.get_nodes().len(),
+                        stats: stats.clone(),
+                    });
+                }
+            }
+        }
+        results
+    }
+}
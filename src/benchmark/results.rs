@@ -0,0 +1,203 @@
+use crate::planner::PlannerStats;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Outcome of a single run of a planner on a problem.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrialResult {
+    pub problem: String,
+    pub planner: String,
+    pub trial: usize,
+    pub seed: u64,
+    pub is_solved: bool,
+    /// Wall-clock time of `plan` in seconds.
+    pub planning_time: f32,
+    /// Cost of the final path, or `None` if the goal was not reached.
+    pub cost: Option<f32>,
+    pub num_nodes: usize,
+    pub stats: PlannerStats,
+}
+
+/// Aggregate of the trials of a planner on a problem. Means over solutions are taken over the
+/// solved trials only, and are `None` if there are none.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BenchmarkSummary {
+    pub problem: String,
+    pub planner: String,
+    pub num_trials: usize,
+    pub success_rate: f32,
+    pub mean_planning_time: f32,
+    pub mean_time_to_first_solution: Option<f32>,
+    pub mean_cost: Option<f32>,
+    pub mean_num_nodes: f32,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BenchmarkResults {
+    pub trials: Vec<TrialResult>,
+}
+
+fn calc_mean(values: &[f32]) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f32>() / values.len() as f32)
+}
+
+fn format_option(value: Option<f32>) -> String {
+    value.map_or(String::new(), |value| value.to_string())
+}
+
+/// Returns `field` as a CSV field, quoted as in RFC 4180 if it contains a comma, a double quote
+/// or a line break.
+pub fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl BenchmarkResults {
+    pub fn new() -> Self {
+        BenchmarkResults { trials: Vec::new() }
+    }
+
+    /// Returns a summary for each pair of problem and planner, in the order they were run.
+    pub fn summarize(&self) -> Vec<BenchmarkSummary> {
+        let mut keys: Vec<(&str, &str)> = Vec::new();
+        for trial in self.trials.iter() {
+            let key = (trial.problem.as_str(), trial.planner.as_str());
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        keys.into_iter()
+            .map(|(problem, planner)| {
+                let trials: Vec<&TrialResult> = self
+                    .trials
+                    .iter()
+                    .filter(|trial| trial.problem == problem && trial.planner == planner)
+                    .collect();
+                let solved_trials: Vec<&TrialResult> = trials
+                    .iter()
+                    .filter(|trial| trial.is_solved)
+                    .cloned()
+                    .collect();
+
+                let planning_times: Vec<f32> =
+                    trials.iter().map(|trial| trial.planning_time).collect();
+                let times_to_first_solution: Vec<f32> = solved_trials
+                    .iter()
+                    .filter_map(|trial| trial.stats.time_to_first_solution)
+                    .collect();
+                let costs: Vec<f32> = solved_trials
+                    .iter()
+                    .filter_map(|trial| trial.cost)
+                    .collect();
+                let nums_nodes: Vec<f32> =
+                    trials.iter().map(|trial| trial.num_nodes as f32).collect();
+
+                BenchmarkSummary {
+                    problem: problem.to_string(),
+                    planner: planner.to_string(),
+                    num_trials: trials.len(),
+                    success_rate: solved_trials.len() as f32 / trials.len() as f32,
+                    mean_planning_time: calc_mean(&planning_times).unwrap_or(0.0),
+                    mean_time_to_first_solution: calc_mean(&times_to_first_solution),
+                    mean_cost: calc_mean(&costs),
+                    mean_num_nodes: calc_mean(&nums_nodes).unwrap_or(0.0),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns one row per trial. Missing values are left empty.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "problem,planner,trial,seed,is_solved,planning_time,time_to_first_solution,cost,num_nodes,\
+             num_iterations,num_collision_checks,num_rejected_samples,num_rejected_edges,nearest_neighbor_time,num_rewires\n",
+        );
+        for trial in self.trials.iter() {
+            csv += &format!(
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                escape_csv_field(&trial.problem),
+                escape_csv_field(&trial.planner),
+                trial.trial,
+                trial.seed,
+                trial.is_solved,
+                trial.planning_time,
+                format_option(trial.stats.time_to_first_solution),
+                format_option(trial.cost),
+                trial.num_nodes,
+                trial.stats.num_iterations,
+                trial.stats.num_collision_checks,
+                trial.stats.num_rejected_samples,
+                trial.stats.num_rejected_edges,
+                trial.stats.nearest_neighbor_time,
+                trial.stats.num_rewires,
+            );
+        }
+        csv
+    }
+
+    /// Returns one row per problem and planner with the output of `summarize`.
+    pub fn summary_to_csv(&self) -> String {
+        let mut csv = String::from(
+            "problem,planner,num_trials,success_rate,mean_planning_time,mean_time_to_first_solution,mean_cost,mean_num_nodes\n",
+        );
+        for summary in self.summarize() {
+            csv += &format!(
+                "{},{},{},{},{},{},{},{}\n",
+                escape_csv_field(&summary.problem),
+                escape_csv_field(&summary.planner),
+                summary.num_trials,
+                summary.success_rate,
+                summary.mean_planning_time,
+                format_option(summary.mean_time_to_first_solution),
+                format_option(summary.mean_cost),
+                summary.mean_num_nodes,
+            );
+        }
+        csv
+    }
+
+    /// Returns the cost-over-time curves with one row per improvement of each trial.
+    pub fn cost_history_to_csv(&self) -> String {
+        let mut csv = String::from("problem,planner,trial,time,cost\n");
+        for trial in self.trials.iter() {
+            for (time, cost) in trial.stats.cost_history.iter() {
+                csv += &format!(
+                    "{},{},{},{},{}\n",
+                    escape_csv_field(&trial.problem),
+                    escape_csv_field(&trial.planner),
+                    trial.trial,
+                    time,
+                    cost
+                );
+            }
+        }
+        csv
+    }
+
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_csv())?;
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
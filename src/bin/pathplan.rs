@@ -0,0 +1,561 @@
+extern crate path_planning as pp;
+use pp::benchmark::escape_csv_field;
+use pp::env::Env;
+use pp::path::Path;
+use pp::planner::AbstractRRT;
+use pp::planner::PlannerStats;
+use pp::plot::{AnimationMode, AnimationOptions, Figure, PlotBuilder, PlotBuilder3d};
+use pp::scenario::{PlannerKind, Scenario};
+use serde::Serialize;
+use std::error::Error;
+use std::process;
+use std::time::Instant;
+
+const USAGE: &str = "Usage: pathplan <SCENARIO> [OPTIONS]
+
+Plans a path for each query of a JSON or TOML scenario file and prints the results.
+
+Options:
+  --planner <NAME>     Planner to use instead of the scenario one: rrt, rrtstar, informed-rrtstar
+  --query <INDEX>      Plan only the query at INDEX
+  --dim <D>            Expected dimension of the scenario: 2 or 3 [default: length of its bounds]
+  --format <FORMAT>    Output format: json or csv [default: json]
+  --summary <FILE>     Write a CSV summary with one row per query to FILE
+  --max-iter <N>       Override the maximum number of iterations
+  --step-size <SIZE>   Override the step size
+  --max-time <SEC>     Stop each planner after SEC seconds
+  --seed <SEED>        Seed the random number generator for reproducible results
+  --png <FILE>         Render the environment, the tree and the path of the first planned query
+  --gif <FILE>         Render an animation of the first planned query (2D only)
+  --animation <MODE>   Animation to render: tree, solution or agent [default: tree]
+  --fps <FPS>          Frame rate of the animation [default: 20]
+  --show-ellipse       Draw the informed ellipse of the best solution in the tree and solution
+                       animations
+  --agent-radius <R>   Radius of the agent of the agent animation [default: 1]
+  --agent-speed <V>    Speed of the agent of the agent animation in units per second [default: 10]
+  --frame-step <N>     Render only every N-th frame of the animation [default: 1]
+  --obj <FILE>         Export the obstacles, the tree and the path of the first planned query as a
+                       Wavefront OBJ file (3D only)
+  -h, --help           Print this message";
+
+enum Format {
+    Json,
+    Csv,
+}
+
+struct Args {
+    scenario_path: String,
+    planner: Option<PlannerKind>,
+    query: Option<usize>,
+    dim: Option<usize>,
+    format: Format,
+    max_iter: Option<usize>,
+    step_size: Option<f32>,
+    max_time: Option<f32>,
+    seed: Option<u64>,
+    summary_path: Option<String>,
+    png_path: Option<String>,
+    gif_path: Option<String>,
+    animation: AnimationOptions,
+    agent_radius: f32,
+    agent_speed: f32,
+    obj_path: Option<String>,
+}
+
+fn parse_args(raw_args: &[String]) -> Result<Args, Box<dyn Error>> {
+    let mut scenario_path: Option<String> = None;
+    let mut args = Args {
+        scenario_path: String::new(),
+        planner: None,
+        query: None,
+        dim: None,
+        format: Format::Json,
+        max_iter: None,
+        step_size: None,
+        max_time: None,
+        seed: None,
+        summary_path: None,
+        png_path: None,
+        gif_path: None,
+        animation: AnimationOptions::default(),
+        agent_radius: 1.0,
+        agent_speed: 10.0,
+        obj_path: None,
+    };
+
+    let mut iter = raw_args.iter();
+    while let Some(arg) = iter.next() {
+        if !arg.starts_with('-') {
+            if scenario_path.is_some() {
+                return Err(format!("unexpected argument: {}", arg).into());
+            }
+            scenario_path = Some(arg.clone());
+            continue;
+        }
+
+        let mut value = || {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--planner" => {
+                let name = value()?;
+                args.planner = Some(
+                    PlannerKind::from_name(&name)
+                        .ok_or_else(|| format!("unknown planner: {}", name))?,
+                );
+            }
+            "--query" => args.query = Some(value()?.parse()?),
+            "--dim" => args.dim = Some(value()?.parse()?),
+            "--format" => {
+                args.format = match value()?.as_str() {
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    format => return Err(format!("unknown format: {}", format).into()),
+                }
+            }
+            "--max-iter" => args.max_iter = Some(value()?.parse()?),
+            "--step-size" => args.step_size = Some(value()?.parse()?),
+            "--max-time" => args.max_time = Some(value()?.parse()?),
+            "--seed" => args.seed = Some(value()?.parse()?),
+            "--summary" => args.summary_path = Some(value()?),
+            "--png" => args.png_path = Some(value()?),
+            "--gif" => args.gif_path = Some(value()?),
+            "--animation" => {
+                args.animation.mode = match value()?.as_str() {
+                    "tree" => AnimationMode::TreeGrowth,
+                    "solution" => AnimationMode::SolutionImprovement,
+                    "agent" => AnimationMode::PathExecution {
+                        radius: 1.0,
+                        speed: 10.0,
+                    },
+                    mode => return Err(format!("unknown animation: {}", mode).into()),
+                }
+            }
+            "--fps" => args.animation.fps = value()?.parse()?,
+            "--show-ellipse" => args.animation.is_ellipse_shown = true,
+            "--agent-radius" => args.agent_radius = value()?.parse()?,
+            "--agent-speed" => args.agent_speed = value()?.parse()?,
+            "--frame-step" => args.animation.frame_step = value()?.parse()?,
+            "--obj" => args.obj_path = Some(value()?),
+            _ => return Err(format!("unknown option: {}", arg).into()),
+        }
+    }
+
+    // The agent options may be given before or after --animation
+    if let AnimationMode::PathExecution { radius, speed } = &mut args.animation.mode {
+        *radius = args.agent_radius;
+        *speed = args.agent_speed;
+    }
+
+    args.scenario_path = scenario_path.ok_or("missing scenario file")?;
+    Ok(args)
+}
+
+#[derive(Serialize)]
+struct QueryResult {
+    query: usize,
+    planner: &'static str,
+    is_solved: bool,
+    length: f32,
+    num_waypoints: usize,
+    num_nodes: usize,
+    planning_time: f64,
+    stats: PlannerStats,
+    path: Vec<Vec<f32>>,
+}
+
+/// Planners are kept alongside the results so that their trees can be rendered.
+struct PlannedQueries<const D: usize> {
+    env: Env<D>,
+    planners: Vec<Box<dyn AbstractRRT<D>>>,
+    results: Vec<QueryResult>,
+}
+
+/// Runs the planner of each selected query.
+fn plan_queries<const D: usize>(
+    scenario: &mut Scenario<D>,
+    args: &Args,
+) -> Result<PlannedQueries<D>, Box<dyn Error>> {
+    if let Some(planner) = args.planner {
+        scenario.planner.kind = planner;
+    }
+    if let Some(max_iter) = args.max_iter {
+        scenario.planner.max_iter = max_iter;
+    }
+    if let Some(step_size) = args.step_size {
+        scenario.planner.step_size = step_size;
+    }
+
+    let query_indices: Vec<usize> = match args.query {
+        Some(index) if index < scenario.queries.len() => vec![index],
+        Some(index) => return Err(format!("query {} does not exist", index).into()),
+        None => (0..scenario.queries.len()).collect(),
+    };
+
+    if let Some(seed) = args.seed {
+        pp::planner::set_seed(seed);
+    }
+
+    let env = scenario.create_env();
+    let mut planners: Vec<Box<dyn AbstractRRT<D>>> = Vec::new();
+    let mut results: Vec<QueryResult> = Vec::new();
+    for index in query_indices {
+        let mut planner = scenario.create_planner(&env, index);
+        planner.set_max_time(args.max_time);
+        if args.gif_path.is_some() && planners.is_empty() {
+            planner.enable_logging();
+        }
+
+        let start_time = Instant::now();
+        let waypoints = planner.plan();
+        let planning_time = start_time.elapsed().as_secs_f64();
+
+        let is_solved = waypoints.len() >= 2 && waypoints.last() == Some(planner.get_goal());
+        results.push(QueryResult {
+            query: index,
+            planner: scenario.planner.kind.get_name(),
+            is_solved,
+            length: Path::new(waypoints.clone()).get_length(),
+            num_waypoints: waypoints.len(),
+            num_nodes: planner.get_nodes().len(),
+            planning_time,
+            stats: planner.get_stats().clone(),
+            path: waypoints.iter().map(|waypoint| waypoint.to_vec()).collect(),
+        });
+        planners.push(planner);
+    }
+
+    Ok(PlannedQueries {
+        env,
+        planners,
+        results,
+    })
+}
+
+/// Prints the results as JSON, or the waypoints as CSV with one row per waypoint.
+fn print_results(results: &[QueryResult], format: &Format) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(results)?),
+        Format::Csv => {
+            let dim = results
+                .iter()
+                .find_map(|result| result.path.first())
+                .map_or(0, |waypoint| waypoint.len());
+            let mut header = vec!["query".to_string(), "index".to_string()];
+            header.extend((0..dim).map(|i| format!("x{}", i)));
+            println!("{}", header.join(","));
+
+            for result in results.iter() {
+                for (k, waypoint) in result.path.iter().enumerate() {
+                    let mut row = vec![result.query.to_string(), k.to_string()];
+                    row.extend(waypoint.iter().map(|x| x.to_string()));
+                    println!("{}", row.join(","));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns one row per query with the outcome and statistics of its planner. Missing values are
+/// left empty.
+fn summary_to_csv(results: &[QueryResult]) -> String {
+    let mut csv = String::from(
+        "query,planner,is_solved,length,num_waypoints,num_nodes,planning_time,time_to_first_solution,\
+         num_iterations,num_collision_checks,num_rejected_samples,num_rejected_edges,nearest_neighbor_time,num_rewires\n",
+    );
+    for result in results.iter() {
+        csv += &format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            result.query,
+            escape_csv_field(result.planner),
+            result.is_solved,
+            result.length,
+            result.num_waypoints,
+            result.num_nodes,
+            result.planning_time,
+            result
+                .stats
+                .time_to_first_solution
+                .map_or(String::new(), |time| time.to_string()),
+            result.stats.num_iterations,
+            result.stats.num_collision_checks,
+            result.stats.num_rejected_samples,
+            result.stats.num_rejected_edges,
+            result.stats.nearest_neighbor_time,
+            result.stats.num_rewires,
+        );
+    }
+    csv
+}
+
+fn write_summary(results: &[QueryResult], args: &Args) -> Result<(), Box<dyn Error>> {
+    if let Some(summary_path) = &args.summary_path {
+        std::fs::write(summary_path, summary_to_csv(results))?;
+    }
+    Ok(())
+}
+
+fn render(
+    env: &Env<2>,
+    planner: &dyn AbstractRRT<2>,
+    path: &[[f32; 2]],
+    args: &Args,
+) -> Result<(), Box<dyn Error>> {
+    if let Some(png_path) = &args.png_path {
+        PlotBuilder::new(env)
+            .tree(planner.get_nodes())
+            .path(path, "path")
+            .start(planner.get_nodes()[0].position)
+            .goal(*planner.get_goal())
+            .save(png_path, pp::plot::DEFAULT_SIZE)?;
+    }
+    if let Some(gif_path) = &args.gif_path {
+        pp::plot::animate_path_to(env, planner.get_log(), gif_path, &args.animation)?;
+    }
+    Ok(())
+}
+
+fn render_3d(
+    env: &Env<3>,
+    planner: &dyn AbstractRRT<3>,
+    path: &[[f32; 3]],
+    args: &Args,
+) -> Result<(), Box<dyn Error>> {
+    if let Some(png_path) = &args.png_path {
+        PlotBuilder3d::new(env)
+            .tree(planner.get_nodes())
+            .path(path, "path")
+            .start(planner.get_nodes()[0].position)
+            .goal(*planner.get_goal())
+            .save(png_path, pp::plot::DEFAULT_SIZE)?;
+    }
+    if let Some(obj_path) = &args.obj_path {
+        let meshes = [
+            pp::plot::create_env_mesh(env),
+            pp::plot::create_tree_mesh(planner.get_nodes()),
+            pp::plot::create_path_mesh(path),
+        ];
+        pp::plot::save_obj(obj_path, &meshes)?;
+    }
+    Ok(())
+}
+
+/// Returns the dimension of a scenario file from the length of its `low` and `high` bounds.
+fn read_scenario_dim(scenario_path: &str) -> Result<usize, Box<dyn Error>> {
+    let s = std::fs::read_to_string(scenario_path)?;
+    let scenario: serde_json::Value = match std::path::Path::new(scenario_path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        Some("json") => serde_json::from_str(&s)?,
+        Some("toml") => toml::from_str(&s)?,
+        _ => return Err(format!("unsupported scenario file: {}", scenario_path).into()),
+    };
+    scenario_dim(&scenario)
+}
+
+fn scenario_dim(scenario: &serde_json::Value) -> Result<usize, Box<dyn Error>> {
+    let bound_len = |key: &str| {
+        scenario
+            .get(key)
+            .and_then(|bound| bound.as_array())
+            .map(|bound| bound.len())
+            .ok_or_else(|| format!("scenario has no `{}` array", key))
+    };
+    let (low_len, high_len) = (bound_len("low")?, bound_len("high")?);
+    if low_len != high_len {
+        return Err(format!(
+            "scenario bounds differ in length: `low` has {} values, `high` has {}",
+            low_len, high_len
+        )
+        .into());
+    }
+    Ok(low_len)
+}
+
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let dim = read_scenario_dim(&args.scenario_path)?;
+    if let Some(expected_dim) = args.dim {
+        if expected_dim != dim {
+            return Err(format!(
+                "--dim {} does not match the scenario, whose bounds are {}D",
+                expected_dim, dim
+            )
+            .into());
+        }
+    }
+
+    match dim {
+        2 => {
+            if args.obj_path.is_some() {
+                return Err("OBJ export is only supported for 3D scenarios".into());
+            }
+            let mut scenario: Scenario<2> = Scenario::load(&args.scenario_path)?;
+            let planned = plan_queries(&mut scenario, args)?;
+            print_results(&planned.results, &args.format)?;
+            write_summary(&planned.results, args)?;
+            if let (Some(planner), Some(result)) =
+                (planned.planners.first(), planned.results.first())
+            {
+                let path: Vec<[f32; 2]> = result.path.iter().map(|p| [p[0], p[1]]).collect();
+                render(&planned.env, planner.as_ref(), &path, args)?;
+            }
+        }
+        3 => {
+            if args.gif_path.is_some() {
+                return Err("animation is only supported for 2D scenarios".into());
+            }
+            let mut scenario: Scenario<3> = Scenario::load(&args.scenario_path)?;
+            let planned = plan_queries(&mut scenario, args)?;
+            print_results(&planned.results, &args.format)?;
+            write_summary(&planned.results, args)?;
+            if let (Some(planner), Some(result)) =
+                (planned.planners.first(), planned.results.first())
+            {
+                let path: Vec<[f32; 3]> = result.path.iter().map(|p| [p[0], p[1], p[2]]).collect();
+                render_3d(&planned.env, planner.as_ref(), &path, args)?;
+            }
+        }
+        dim => return Err(format!("unsupported dimension: {}", dim).into()),
+    }
+    Ok(())
+}
+
+fn main() {
+    let raw_args: Vec<String> = std::env::args().skip(1).collect();
+    if raw_args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let result = parse_args(&raw_args).and_then(|args| run(&args));
+    if let Err(error) = result {
+        eprintln!("error: {}\n\n{}", error, USAGE);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn create_result(query: usize) -> QueryResult {
+        QueryResult {
+            query,
+            planner: "rrtstar",
+            is_solved: true,
+            length: 2.5,
+            num_waypoints: 3,
+            num_nodes: 10,
+            planning_time: 0.5,
+            stats: PlannerStats {
+                num_iterations: 20,
+                num_collision_checks: 40,
+                num_rejected_samples: 5,
+                num_rejected_edges: 2,
+                time_to_first_solution: Some(0.25),
+                ..Default::default()
+            },
+            path: vec![vec![0.0, 0.0], vec![1.0, 1.0], vec![2.0, 1.0]],
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse_args(&to_args(&[
+            "scenario.toml",
+            "--planner",
+            "informed-rrtstar",
+            "--dim",
+            "3",
+            "--query",
+            "1",
+            "--animation",
+            "agent",
+            "--agent-radius",
+            "2",
+        ]))
+        .unwrap();
+        assert_eq!(args.scenario_path, "scenario.toml");
+        assert!(matches!(args.planner, Some(PlannerKind::InformedRRTStar)));
+        assert_eq!(args.dim, Some(3));
+        assert_eq!(args.query, Some(1));
+        assert!(matches!(
+            args.animation.mode,
+            AnimationMode::PathExecution { radius, speed } if radius == 2.0 && speed == 10.0
+        ));
+
+        let args = parse_args(&to_args(&["scenario.toml"])).unwrap();
+        assert_eq!(args.dim, None);
+        assert!(args.planner.is_none());
+    }
+
+    #[test]
+    fn test_parse_invalid_args() {
+        assert!(parse_args(&to_args(&["scenario.toml", "--dim", "two"])).is_err());
+        assert!(parse_args(&to_args(&["scenario.toml", "--dim"])).is_err());
+
+        let error = parse_args(&to_args(&["scenario.toml", "--planner", "prm"]))
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "unknown planner: prm");
+
+        let error = parse_args(&to_args(&["--planner", "rrt"])).err().unwrap();
+        assert_eq!(error.to_string(), "missing scenario file");
+
+        assert!(parse_args(&to_args(&["a.toml", "b.toml"])).is_err());
+        assert!(parse_args(&to_args(&["scenario.toml", "--verbose"])).is_err());
+    }
+
+    #[test]
+    fn test_scenario_dim() {
+        assert_eq!(
+            read_scenario_dim("examples/scenarios/example_2d.json").unwrap(),
+            2
+        );
+        assert_eq!(
+            read_scenario_dim("examples/scenarios/example_3d.toml").unwrap(),
+            3
+        );
+
+        let scenario = serde_json::json!({"low": [0.0, 0.0], "high": [1.0, 1.0, 1.0]});
+        assert!(scenario_dim(&scenario).is_err());
+        assert!(scenario_dim(&serde_json::json!({"high": [1.0, 1.0]})).is_err());
+    }
+
+    #[test]
+    fn test_dim_mismatch() {
+        let args = parse_args(&to_args(&[
+            "examples/scenarios/example_3d.toml",
+            "--dim",
+            "2",
+        ]))
+        .unwrap();
+        let error = run(&args).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "--dim 2 does not match the scenario, whose bounds are 3D"
+        );
+    }
+
+    #[test]
+    fn test_summary_to_csv() {
+        let csv = summary_to_csv(&[create_result(0), create_result(1)]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+
+        let num_columns = lines[0].split(',').count();
+        assert_eq!(num_columns, 14);
+        assert!(lines
+            .iter()
+            .all(|line| line.split(',').count() == num_columns));
+        assert_eq!(lines[1], "0,rrtstar,true,2.5,3,10,0.5,0.25,20,40,5,2,0,0");
+    }
+}
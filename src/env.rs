@@ -1,8 +1,27 @@
 mod obstacle;
+pub use obstacle::create_2d_rotation;
+pub use obstacle::create_identity_rotation;
 pub use obstacle::Obstacle;
 pub use obstacle::Obstacle::CircleObstacle;
 pub use obstacle::Obstacle::RectObstacle;
 
+mod bvh;
 mod env;
 pub use env::create_example_2d_env;
+pub use env::AbstractEnv;
 pub use env::Env;
+mod occupancy_grid;
+pub use occupancy_grid::CellState;
+pub use occupancy_grid::OccupancyGrid;
+#[cfg(feature = "serde")]
+mod ros_map;
+#[cfg(feature = "serde")]
+pub use ros_map::load_ros_map;
+#[cfg(feature = "serde")]
+pub use ros_map::parse_ros_map_yaml;
+#[cfg(feature = "serde")]
+pub use ros_map::RosMapMetadata;
+#[cfg(feature = "serde")]
+pub use ros_map::RosMapMode;
+mod footprint;
+pub use footprint::Footprint;
@@ -0,0 +1,322 @@
+use crate::env::Obstacle;
+
+const MAX_LEAF_SIZE: usize = 4;
+
+enum BvhNodeContent {
+    Children(usize, usize),
+    Leaf(Vec<usize>),
+}
+
+struct BvhNode<const D: usize> {
+    low: [f32; D],
+    high: [f32; D],
+    content: BvhNodeContent,
+}
+
+/// Bounding volume hierarchy of axis-aligned boxes over the obstacles of an environment.
+///
+/// Obstacles that are unbounded along some axis are kept out of the tree and always checked.
+/// Obstacles whose signed distance is only a lower bound are always checked by the distance query,
+/// since their bound can be smaller than the distance to their box.
+pub struct Bvh<const D: usize> {
+    nodes: Vec<BvhNode<D>>,
+    unbounded_indices: Vec<usize>,
+    inexact_indices: Vec<usize>,
+}
+
+fn is_bounded<const D: usize>(low: &[f32; D], high: &[f32; D]) -> bool {
+    (0..D).all(|i| f32::MIN < low[i] && high[i] < f32::MAX)
+}
+
+fn is_signed_distance_exact<const D: usize>(obstacle: &Obstacle<D>) -> bool {
+    match obstacle {
+        Obstacle::IntersectionObstacle { .. } | Obstacle::DifferenceObstacle { .. } => false,
+        Obstacle::UnionObstacle { obstacles } => obstacles.iter().all(is_signed_distance_exact),
+        Obstacle::InflatedObstacle { obstacle, .. } => is_signed_distance_exact(obstacle),
+        _ => true,
+    }
+}
+
+/// Returns the signed distance to the box, which is a lower bound of the signed distance to any
+/// obstacle enclosed by it.
+fn calc_signed_distance_to_aabb<const D: usize>(
+    low: &[f32; D],
+    high: &[f32; D],
+    position: &[f32; D],
+) -> f32 {
+    let outside_distance = (0..D)
+        .map(|i| {
+            (low[i] - position[i])
+                .max(position[i] - high[i])
+                .max(0.0)
+                .powf(2.0)
+        })
+        .sum::<f32>()
+        .powf(0.5);
+    if outside_distance > 0.0 {
+        return outside_distance;
+    }
+
+    -(0..D)
+        .map(|i| (position[i] - low[i]).min(high[i] - position[i]))
+        .fold(f32::MAX, f32::min)
+}
+
+fn is_intersecting_aabb<const D: usize>(
+    low: &[f32; D],
+    high: &[f32; D],
+    from: &[f32; D],
+    to: &[f32; D],
+) -> bool {
+    let mut t_min: f32 = 0.0;
+    let mut t_max: f32 = 1.0;
+    for i in 0..D {
+        let direction = to[i] - from[i];
+        if direction == 0.0 {
+            if from[i] < low[i] || high[i] < from[i] {
+                return false;
+            }
+            continue;
+        }
+
+        let t_0 = (low[i] - from[i]) / direction;
+        let t_1 = (high[i] - from[i]) / direction;
+        t_min = t_min.max(t_0.min(t_1));
+        t_max = t_max.min(t_0.max(t_1));
+        if t_max < t_min {
+            return false;
+        }
+    }
+    true
+}
+
+impl<const D: usize> Bvh<D> {
+    pub fn new(obstacles: &[Obstacle<D>]) -> Self {
+        let mut aabbs: Vec<([f32; D], [f32; D])> = Vec::with_capacity(obstacles.len());
+        let mut bounded_indices: Vec<usize> = Vec::new();
+        let mut unbounded_indices: Vec<usize> = Vec::new();
+        let mut inexact_indices: Vec<usize> = Vec::new();
+        for (i, obstacle) in obstacles.iter().enumerate() {
+            let (low, high) = obstacle.calc_aabb();
+            if !is_bounded(&low, &high) {
+                unbounded_indices.push(i);
+            } else {
+                bounded_indices.push(i);
+                if !is_signed_distance_exact(obstacle) {
+                    inexact_indices.push(i);
+                }
+            }
+            aabbs.push((low, high));
+        }
+
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            unbounded_indices,
+            inexact_indices,
+        };
+        if !bounded_indices.is_empty() {
+            bvh.build_node(&aabbs, bounded_indices);
+        }
+        bvh
+    }
+
+    fn build_node(&mut self, aabbs: &[([f32; D], [f32; D])], mut indices: Vec<usize>) -> usize {
+        let mut low: [f32; D] = [f32::MAX; D];
+        let mut high: [f32; D] = [f32::MIN; D];
+        for &index in indices.iter() {
+            for i in 0..D {
+                low[i] = low[i].min(aabbs[index].0[i]);
+                high[i] = high[i].max(aabbs[index].1[i]);
+            }
+        }
+
+        let node_index = self.nodes.len();
+        if indices.len() <= MAX_LEAF_SIZE {
+            self.nodes.push(BvhNode {
+                low,
+                high,
+                content: BvhNodeContent::Leaf(indices),
+            });
+            return node_index;
+        }
+
+        // Split at the median of the box centers along the longest axis
+        let mut axis = 0;
+        for i in 1..D {
+            if high[i] - low[i] > high[axis] - low[axis] {
+                axis = i;
+            }
+        }
+        let center = |index: &usize| aabbs[*index].0[axis] + aabbs[*index].1[axis];
+        indices.sort_by(|a, b| center(a).total_cmp(&center(b)));
+        let right_indices = indices.split_off(indices.len() / 2);
+
+        self.nodes.push(BvhNode {
+            low,
+            high,
+            content: BvhNodeContent::Leaf(Vec::new()),
+        });
+        let left = self.build_node(aabbs, indices);
+        let right = self.build_node(aabbs, right_indices);
+        self.nodes[node_index].content = BvhNodeContent::Children(left, right);
+        node_index
+    }
+
+    /// Returns whether `predicate` holds for any obstacle whose box passes `is_box_relevant`.
+    fn any<F, G>(&self, is_box_relevant: F, predicate: G) -> bool
+    where
+        F: Fn(&[f32; D], &[f32; D]) -> bool,
+        G: Fn(usize) -> bool,
+    {
+        if self.unbounded_indices.iter().any(|&index| predicate(index)) {
+            return true;
+        }
+        if self.nodes.is_empty() {
+            return false;
+        }
+
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            if !is_box_relevant(&node.low, &node.high) {
+                continue;
+            }
+            match &node.content {
+                BvhNodeContent::Children(left, right) => {
+                    stack.push(*left);
+                    stack.push(*right);
+                }
+                BvhNodeContent::Leaf(indices) => {
+                    if indices.iter().any(|&index| predicate(index)) {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    pub fn is_inside_obstacle(&self, obstacles: &[Obstacle<D>], position: &[f32; D]) -> bool {
+        self.any(
+            |low, high| (0..D).all(|i| low[i] <= position[i] && position[i] <= high[i]),
+            |index| obstacles[index].is_inside(position),
+        )
+    }
+
+    /// Returns whether `predicate` holds for any obstacle whose box overlaps the box from `low`
+    /// to `high`.
+    pub fn any_in_box<F>(&self, low: &[f32; D], high: &[f32; D], predicate: F) -> bool
+    where
+        F: Fn(usize) -> bool,
+    {
+        self.any(
+            |node_low, node_high| (0..D).all(|i| node_low[i] <= high[i] && low[i] <= node_high[i]),
+            predicate,
+        )
+    }
+
+    pub fn is_intersecting_obstacle(
+        &self,
+        obstacles: &[Obstacle<D>],
+        from: &[f32; D],
+        to: &[f32; D],
+    ) -> bool {
+        self.any(
+            |low, high| is_intersecting_aabb(low, high, from, to),
+            |index| obstacles[index].is_intersecting_segment(from, to),
+        )
+    }
+
+    /// Returns the smallest signed distance and the index of its obstacle, skipping the boxes
+    /// that are farther than the best distance found so far.
+    pub fn find_nearest_obstacle(
+        &self,
+        obstacles: &[Obstacle<D>],
+        position: &[f32; D],
+    ) -> Option<(f32, usize)> {
+        let mut nearest: Option<(f32, usize)> = None;
+        let update = |nearest: &mut Option<(f32, usize)>, index: usize| {
+            let distance = obstacles[index].signed_distance(position);
+            match *nearest {
+                Some((min_distance, min_index))
+                    if min_distance < distance
+                        || (min_distance == distance && min_index < index) => {}
+                _ => *nearest = Some((distance, index)),
+            }
+        };
+
+        for &index in self
+            .unbounded_indices
+            .iter()
+            .chain(self.inexact_indices.iter())
+        {
+            update(&mut nearest, index);
+        }
+        if self.nodes.is_empty() {
+            return nearest;
+        }
+
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            let box_distance = calc_signed_distance_to_aabb(&node.low, &node.high, position);
+            if let Some((min_distance, _)) = nearest {
+                if min_distance < box_distance {
+                    continue;
+                }
+            }
+            match &node.content {
+                BvhNodeContent::Children(left, right) => {
+                    // Visit the closer child first to tighten the bound early
+                    let left_distance = calc_signed_distance_to_aabb(
+                        &self.nodes[*left].low,
+                        &self.nodes[*left].high,
+                        position,
+                    );
+                    let right_distance = calc_signed_distance_to_aabb(
+                        &self.nodes[*right].low,
+                        &self.nodes[*right].high,
+                        position,
+                    );
+                    if left_distance < right_distance {
+                        stack.push(*right);
+                        stack.push(*left);
+                    } else {
+                        stack.push(*left);
+                        stack.push(*right);
+                    }
+                }
+                BvhNodeContent::Leaf(indices) => {
+                    for &index in indices.iter() {
+                        update(&mut nearest, index);
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+}
+
+impl<const D: usize> Clone for BvhNode<D> {
+    fn clone(&self) -> BvhNode<D> {
+        BvhNode {
+            low: self.low,
+            high: self.high,
+            content: match &self.content {
+                BvhNodeContent::Children(left, right) => BvhNodeContent::Children(*left, *right),
+                BvhNodeContent::Leaf(indices) => BvhNodeContent::Leaf(indices.clone()),
+            },
+        }
+    }
+}
+
+impl<const D: usize> Clone for Bvh<D> {
+    fn clone(&self) -> Bvh<D> {
+        Bvh {
+            nodes: self.nodes.clone(),
+            unbounded_indices: self.unbounded_indices.clone(),
+            inexact_indices: self.inexact_indices.clone(),
+        }
+    }
+}
@@ -1,11 +1,28 @@
+use crate::env::bvh::Bvh;
 use crate::env::Obstacle;
 use crate::env::Obstacle::CircleObstacle;
 use crate::env::Obstacle::RectObstacle;
 
+pub trait AbstractEnv<const D: usize> {
+    fn get_low(&self) -> &[f32; D];
+    fn get_high(&self) -> &[f32; D];
+    fn is_inside_obstacle(&self, position: &[f32; D]) -> bool;
+    fn is_intersecting_obstacle(&self, from: &[f32; D], to: &[f32; D]) -> bool;
+}
+
+/// Box-bounded environment with obstacles, constructed with `Env::new`.
+///
+/// The obstacles are read with `get_obstacles` and added with `add_obstacle`, so that a bounding
+/// volume hierarchy built with `build_bvh` always covers them.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Env<const D: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
     pub low: [f32; D],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
     pub high: [f32; D],
-    pub obstacles: Vec<Obstacle<D>>,
+    obstacles: Vec<Obstacle<D>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bvh: Option<Bvh<D>>,
 }
 
 impl<const D: usize> Env<D> {
@@ -14,9 +31,37 @@ impl<const D: usize> Env<D> {
             low,
             high,
             obstacles,
+            bvh: None,
         }
     }
+
+    pub fn get_obstacles(&self) -> &[Obstacle<D>] {
+        &self.obstacles
+    }
+
+    /// Adds `obstacle`, rebuilding the bounding volume hierarchy if one was built.
+    pub fn add_obstacle(&mut self, obstacle: Obstacle<D>) {
+        self.obstacles.push(obstacle);
+        if self.bvh.is_some() {
+            self.build_bvh();
+        }
+    }
+
+    /// Builds a bounding volume hierarchy over the obstacles to speed up the point, segment and
+    /// distance queries. It is kept up to date by `add_obstacle` and `inflate`.
+    pub fn build_bvh(&mut self) {
+        self.bvh = Some(Bvh::new(&self.obstacles));
+    }
+
+    fn get_bvh(&self) -> Option<&Bvh<D>> {
+        self.bvh.as_ref()
+    }
+
     pub fn is_inside_obstacle(&self, position: &[f32; D]) -> bool {
+        if let Some(bvh) = self.get_bvh() {
+            return bvh.is_inside_obstacle(&self.obstacles, position);
+        }
+
         for obstacle in self.obstacles.iter() {
             if obstacle.is_inside(position) {
                 return true;
@@ -24,6 +69,86 @@ impl<const D: usize> Env<D> {
         }
         false
     }
+
+    pub fn is_intersecting_obstacle(&self, from: &[f32; D], to: &[f32; D]) -> bool {
+        if let Some(bvh) = self.get_bvh() {
+            return bvh.is_intersecting_obstacle(&self.obstacles, from, to);
+        }
+
+        self.obstacles
+            .iter()
+            .any(|obstacle| obstacle.is_intersecting_segment(from, to))
+    }
+
+    /// Returns whether `predicate` holds for any obstacle that may overlap the box from `low` to
+    /// `high`. Obstacles whose boxes do not overlap it are skipped when a BVH is built.
+    pub(crate) fn any_obstacle_in_box<F>(
+        &self,
+        low: &[f32; D],
+        high: &[f32; D],
+        predicate: F,
+    ) -> bool
+    where
+        F: Fn(&Obstacle<D>) -> bool,
+    {
+        if let Some(bvh) = self.get_bvh() {
+            return bvh.any_in_box(low, high, |index| predicate(&self.obstacles[index]));
+        }
+
+        self.obstacles.iter().any(predicate)
+    }
+
+    /// Returns the environment in which every obstacle is inflated by `radius`, so that a point
+    /// outside the inflated obstacles is safe for a round robot of `radius`.
+    pub fn inflate(&self, radius: f32) -> Env<D> {
+        let obstacles = self
+            .obstacles
+            .iter()
+            .map(|obstacle| obstacle.inflate(radius))
+            .collect();
+        let mut env = Env::new(self.low, self.high, obstacles);
+        if self.get_bvh().is_some() {
+            env.build_bvh();
+        }
+        env
+    }
+
+    /// Returns the signed distance to the nearest obstacle, its index and the gradient of the
+    /// distance, or `None` if there are no obstacles.
+    pub fn distance_to_nearest_obstacle(
+        &self,
+        position: &[f32; D],
+    ) -> Option<(f32, usize, [f32; D])> {
+        let mut nearest: Option<(f32, usize)> = None;
+        if let Some(bvh) = self.get_bvh() {
+            nearest = bvh.find_nearest_obstacle(&self.obstacles, position);
+        } else {
+            for (i, obstacle) in self.obstacles.iter().enumerate() {
+                let distance = obstacle.signed_distance(position);
+                match nearest {
+                    Some((min_distance, _)) if min_distance <= distance => {}
+                    _ => nearest = Some((distance, i)),
+                }
+            }
+        }
+
+        nearest.map(|(distance, i)| (distance, i, self.obstacles[i].calc_gradient(position)))
+    }
+}
+
+impl<const D: usize> AbstractEnv<D> for Env<D> {
+    fn get_low(&self) -> &[f32; D] {
+        &self.low
+    }
+    fn get_high(&self) -> &[f32; D] {
+        &self.high
+    }
+    fn is_inside_obstacle(&self, position: &[f32; D]) -> bool {
+        Env::is_inside_obstacle(self, position)
+    }
+    fn is_intersecting_obstacle(&self, from: &[f32; D], to: &[f32; D]) -> bool {
+        Env::is_intersecting_obstacle(self, from, to)
+    }
 }
 
 pub fn create_example_2d_env() -> Env<2> {
@@ -69,11 +194,7 @@ pub fn create_example_2d_env() -> Env<2> {
         },
     ];
 
-    Env {
-        low,
-        high,
-        obstacles,
-    }
+    Env::new(low, high, obstacles)
 }
 
 impl<const D: usize> Clone for Env<D> {
@@ -83,6 +204,7 @@ impl<const D: usize> Clone for Env<D> {
             low: self.low.clone(),
             high: self.high.clone(),
             obstacles,
+            bvh: self.bvh.clone(),
         }
     }
 }
@@ -0,0 +1,157 @@
+use crate::env::CellState;
+use crate::env::Env;
+use crate::env::Obstacle;
+use crate::env::OccupancyGrid;
+
+/// Polygonal robot footprint given in the robot frame, checked at (x, y, theta) poses.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Footprint {
+    pub vertices: Vec<[f32; 2]>,
+}
+
+impl Footprint {
+    /// Panics if the vertices do not form a convex polygon, which `is_in_collision` relies on.
+    pub fn new(vertices: Vec<[f32; 2]>) -> Self {
+        assert!(
+            is_convex(&vertices),
+            "the footprint must be a convex polygon"
+        );
+        Footprint { vertices }
+    }
+
+    /// Rectangle of `length` along the heading and `width` across it, centered at the origin.
+    pub fn create_rect(length: f32, width: f32) -> Self {
+        Footprint::new(vec![
+            [-length / 2.0, -width / 2.0],
+            [length / 2.0, -width / 2.0],
+            [length / 2.0, width / 2.0],
+            [-length / 2.0, width / 2.0],
+        ])
+    }
+
+    pub fn get_vertices_at(&self, pose: &[f32; 3]) -> Vec<[f32; 2]> {
+        let (sin, cos) = pose[2].sin_cos();
+        self.vertices
+            .iter()
+            .map(|v| {
+                [
+                    pose[0] + cos * v[0] - sin * v[1],
+                    pose[1] + sin * v[0] + cos * v[1],
+                ]
+            })
+            .collect()
+    }
+
+    /// Returns whether the footprint at `pose` overlaps any obstacle of `env`. The queries go
+    /// through `env`, so they use its BVH if one was built.
+    ///
+    /// An obstacle overlaps the footprint if it crosses an edge, contains a vertex, or lies
+    /// inside the footprint. The last case is checked for the obstacles near the footprint with
+    /// the point of the obstacle closest to the pose, estimated from its signed distance and
+    /// gradient, which lies inside the footprint because the footprint is convex.
+    pub fn is_in_collision(&self, env: &Env<2>, pose: &[f32; 3]) -> bool {
+        let vertices = self.get_vertices_at(pose);
+        let is_crossing_edge = (0..vertices.len()).any(|k| {
+            let next = (k + 1) % vertices.len();
+            env.is_intersecting_obstacle(&vertices[k], &vertices[next])
+        });
+        if is_crossing_edge || env.is_inside_obstacle(&vertices[0]) {
+            return true;
+        }
+
+        let (low, high) = calc_bounds(&vertices);
+        let polygon = Obstacle::PolygonObstacle { vertices };
+        let center = [pose[0], pose[1]];
+        env.any_obstacle_in_box(&low, &high, |obstacle| {
+            let distance = obstacle.signed_distance(&center);
+            if distance <= 0.0 {
+                return true;
+            }
+            let gradient = obstacle.calc_gradient(&center);
+            let closest_point = [
+                center[0] - distance * gradient[0],
+                center[1] - distance * gradient[1],
+            ];
+            polygon.is_inside(&closest_point)
+        })
+    }
+
+    /// Returns whether the footprint at `pose` overlaps any blocking cell of `grid`.
+    pub fn is_in_collision_with_grid(&self, grid: &OccupancyGrid<2>, pose: &[f32; 3]) -> bool {
+        let vertices = self.get_vertices_at(pose);
+        let is_crossing_edge = (0..vertices.len()).any(|k| {
+            let next = (k + 1) % vertices.len();
+            grid.is_intersecting_obstacle(&vertices[k], &vertices[next])
+        });
+        if is_crossing_edge {
+            return true;
+        }
+
+        // Cells inside the footprint that no edge crosses
+        let (low, high) = calc_bounds(&vertices);
+        let polygon = Obstacle::PolygonObstacle { vertices };
+        let mut low_index = [0; 2];
+        let mut high_index = [0; 2];
+        for i in 0..2 {
+            let to_index = |value: f32| {
+                let k = ((value - grid.get_origin()[i]) / grid.get_resolution()).floor();
+                k.clamp(0.0, grid.get_shape()[i] as f32 - 1.0) as usize
+            };
+            low_index[i] = to_index(low[i]);
+            high_index[i] = to_index(high[i]);
+        }
+
+        for x in low_index[0]..=high_index[0] {
+            for y in low_index[1]..=high_index[1] {
+                let is_blocking = match grid.get_cell(&[x, y]) {
+                    CellState::Free => false,
+                    CellState::Occupied => true,
+                    CellState::Unknown => grid.is_unknown_occupied,
+                };
+                if is_blocking && polygon.is_inside(&grid.get_cell_center(&[x, y])) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+/// Returns whether the polygon turns the same way at every vertex, allowing straight angles.
+fn is_convex(vertices: &[[f32; 2]]) -> bool {
+    let n = vertices.len();
+    let mut sign = 0.0;
+    for k in 0..n {
+        let (a, b, c) = (vertices[k], vertices[(k + 1) % n], vertices[(k + 2) % n]);
+        let cross = (b[0] - a[0]) * (c[1] - b[1]) - (b[1] - a[1]) * (c[0] - b[0]);
+        if cross == 0.0 {
+            continue;
+        }
+        if sign * cross < 0.0 {
+            return false;
+        }
+        sign = cross.signum();
+    }
+    true
+}
+
+fn calc_bounds(vertices: &[[f32; 2]]) -> ([f32; 2], [f32; 2]) {
+    let mut low = [f32::MAX; 2];
+    let mut high = [f32::MIN; 2];
+    for vertex in vertices.iter() {
+        for i in 0..2 {
+            low[i] = low[i].min(vertex[i]);
+            high[i] = high[i].max(vertex[i]);
+        }
+    }
+    (low, high)
+}
+
+impl Clone for Footprint {
+    fn clone(&self) -> Footprint {
+        Footprint {
+            vertices: self.vertices.clone(),
+        }
+    }
+}
@@ -1,6 +1,82 @@
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
 pub enum Obstacle<const D: usize> {
-    RectObstacle { center: [f32; D], size: [f32; D] },
-    CircleObstacle { center: [f32; D], radius: f32 },
+    RectObstacle {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+        center: [f32; D],
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+        size: [f32; D],
+    },
+    CircleObstacle {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+        center: [f32; D],
+        radius: f32,
+    },
+    /// Simple polygon in the plane of the first two axes, extruded along the other axes.
+    PolygonObstacle {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::vec_array"))]
+        vertices: Vec<[f32; D]>,
+    },
+    /// Convex polytope `{x | normals[k] . x <= offsets[k] for all k}`.
+    PolytopeObstacle {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::vec_array"))]
+        normals: Vec<[f32; D]>,
+        offsets: Vec<f32>,
+    },
+    /// Box whose axes are the columns of `rotation`.
+    OrientedRectObstacle {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+        center: [f32; D],
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+        size: [f32; D],
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::matrix"))]
+        rotation: [[f32; D]; D],
+    },
+    /// Ellipsoid whose axes are the columns of `rotation`.
+    EllipsoidObstacle {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+        center: [f32; D],
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+        radiuses: [f32; D],
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::matrix"))]
+        rotation: [[f32; D]; D],
+    },
+    /// Sphere swept along the segment from `start` to `end`.
+    CapsuleObstacle {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+        start: [f32; D],
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+        end: [f32; D],
+        radius: f32,
+    },
+    UnionObstacle {
+        obstacles: Vec<Obstacle<D>>,
+    },
+    IntersectionObstacle {
+        obstacles: Vec<Obstacle<D>>,
+    },
+    /// `base` with all of the `subtracted` obstacles cut out of it.
+    DifferenceObstacle {
+        base: Box<Obstacle<D>>,
+        subtracted: Vec<Obstacle<D>>,
+    },
+    /// Minkowski sum of `obstacle` and a ball of `radius`.
+    InflatedObstacle {
+        obstacle: Box<Obstacle<D>>,
+        radius: f32,
+    },
+}
+
+pub fn create_identity_rotation<const D: usize>() -> [[f32; D]; D] {
+    let mut rotation: [[f32; D]; D] = [[0.0; D]; D];
+    for (i, row) in rotation.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+    rotation
+}
+
+pub fn create_2d_rotation(angle: f32) -> [[f32; 2]; 2] {
+    [[angle.cos(), -angle.sin()], [angle.sin(), angle.cos()]]
 }
 
 impl<const D: usize> Obstacle<D> {
@@ -26,8 +102,834 @@ impl<const D: usize> Obstacle<D> {
 
                 distance <= *radius
             }
+            Obstacle::PolygonObstacle { vertices } => {
+                is_inside_polygon(vertices, (position[0], position[1]))
+                    || calc_distance_to_polygon_edges(vertices, (position[0], position[1])) == 0.0
+            }
+            Obstacle::PolytopeObstacle { normals, offsets } => {
+                for (normal, offset) in normals.iter().zip(offsets.iter()) {
+                    if dot(normal, position) > *offset {
+                        return false;
+                    }
+                }
+
+                true
+            }
+            Obstacle::OrientedRectObstacle {
+                center,
+                size,
+                rotation,
+            } => {
+                let local_position = to_local_frame(center, rotation, position);
+                (0..D).all(|i| local_position[i].abs() <= size[i] / 2.0)
+            }
+            Obstacle::EllipsoidObstacle {
+                center,
+                radiuses,
+                rotation,
+            } => {
+                let local_position = to_local_frame(center, rotation, position);
+                (0..D)
+                    .map(|i| (local_position[i] / radiuses[i]).powf(2.0))
+                    .sum::<f32>()
+                    <= 1.0
+            }
+            Obstacle::CapsuleObstacle { start, end, radius } => {
+                let mut _position: [f32; D] = [0.0; D];
+                _position.copy_from_slice(&position[..D]);
+                let closest_point = calc_closest_point_on_segment(start, end, &_position);
+                calc_distance(&closest_point, &_position) <= *radius
+            }
+            Obstacle::UnionObstacle { obstacles } => obstacles
+                .iter()
+                .any(|obstacle| obstacle.is_inside(position)),
+            Obstacle::IntersectionObstacle { obstacles } => obstacles
+                .iter()
+                .all(|obstacle| obstacle.is_inside(position)),
+            Obstacle::DifferenceObstacle { base, subtracted } => {
+                base.is_inside(position)
+                    && !subtracted
+                        .iter()
+                        .any(|obstacle| obstacle.is_inside(position))
+            }
+            Obstacle::InflatedObstacle { obstacle, radius } => {
+                let mut _position: [f32; D] = [0.0; D];
+                _position.copy_from_slice(&position[..D]);
+                obstacle.signed_distance(&_position) <= *radius
+            }
+        }
+    }
+
+    /// Returns whether the segment from `from` to `to` touches the obstacle.
+    pub fn is_intersecting_segment(&self, from: &[f32; D], to: &[f32; D]) -> bool {
+        match self {
+            Obstacle::RectObstacle { center, size } => {
+                // Clip the segment with each slab
+                let mut t_min: f32 = 0.0;
+                let mut t_max: f32 = 1.0;
+                for i in 0..D {
+                    let low = center[i] - size[i] / 2.0;
+                    let high = center[i] + size[i] / 2.0;
+                    let direction = to[i] - from[i];
+                    if direction == 0.0 {
+                        if from[i] < low || high < from[i] {
+                            return false;
+                        }
+                        continue;
+                    }
+
+                    let t_0 = (low - from[i]) / direction;
+                    let t_1 = (high - from[i]) / direction;
+                    t_min = t_min.max(t_0.min(t_1));
+                    t_max = t_max.min(t_0.max(t_1));
+                    if t_max < t_min {
+                        return false;
+                    }
+                }
+
+                true
+            }
+            Obstacle::CircleObstacle { center, radius } => {
+                let closest_point = calc_closest_point_on_segment(from, to, center);
+                calc_distance(&closest_point, center) <= *radius
+            }
+            Obstacle::PolygonObstacle { vertices } => {
+                let (a, b) = ((from[0], from[1]), (to[0], to[1]));
+                if is_inside_polygon(vertices, a) {
+                    return true;
+                }
+
+                (0..vertices.len()).any(|k| {
+                    let next = (k + 1) % vertices.len();
+                    is_intersecting_segments_2d(
+                        a,
+                        b,
+                        (vertices[k][0], vertices[k][1]),
+                        (vertices[next][0], vertices[next][1]),
+                    )
+                })
+            }
+            Obstacle::PolytopeObstacle { normals, offsets } => {
+                // Clip the segment with each half-space
+                let mut t_min: f32 = 0.0;
+                let mut t_max: f32 = 1.0;
+                for (normal, offset) in normals.iter().zip(offsets.iter()) {
+                    let start = dot(normal, from) - offset;
+                    let slope = dot(normal, to) - dot(normal, from);
+                    if slope == 0.0 {
+                        if start > 0.0 {
+                            return false;
+                        }
+                        continue;
+                    }
+
+                    let t = -start / slope;
+                    if slope > 0.0 {
+                        t_max = t_max.min(t);
+                    } else {
+                        t_min = t_min.max(t);
+                    }
+                    if t_max < t_min {
+                        return false;
+                    }
+                }
+
+                true
+            }
+            Obstacle::OrientedRectObstacle {
+                center,
+                size,
+                rotation,
+            } => {
+                let local_rect = Obstacle::RectObstacle {
+                    center: [0.0; D],
+                    size: *size,
+                };
+                local_rect.is_intersecting_segment(
+                    &to_local_frame(center, rotation, from),
+                    &to_local_frame(center, rotation, to),
+                )
+            }
+            Obstacle::EllipsoidObstacle {
+                center,
+                radiuses,
+                rotation,
+            } => {
+                // Scale the ellipsoid to the unit ball
+                let mut local_from = to_local_frame(center, rotation, from);
+                let mut local_to = to_local_frame(center, rotation, to);
+                for i in 0..D {
+                    local_from[i] /= radiuses[i];
+                    local_to[i] /= radiuses[i];
+                }
+
+                let closest_point =
+                    calc_closest_point_on_segment(&local_from, &local_to, &[0.0; D]);
+                calc_distance(&closest_point, &[0.0; D]) <= 1.0
+            }
+            Obstacle::CapsuleObstacle { start, end, radius } => {
+                calc_distance_between_segments(start, end, from, to) <= *radius
+            }
+            Obstacle::UnionObstacle { obstacles } => obstacles
+                .iter()
+                .any(|obstacle| obstacle.is_intersecting_segment(from, to)),
+            Obstacle::IntersectionObstacle { .. }
+            | Obstacle::DifferenceObstacle { .. }
+            | Obstacle::InflatedObstacle { .. } => {
+                self.is_intersecting_segment_by_sphere_tracing(from, to)
+            }
+        }
+    }
+
+    /// Marches along the segment with steps of the signed distance, which never overshoots the
+    /// surface because the signed distance never exceeds the true distance.
+    fn is_intersecting_segment_by_sphere_tracing(&self, from: &[f32; D], to: &[f32; D]) -> bool {
+        let tol = 1E-4;
+        let max_iter = 10000;
+
+        let length = calc_distance(from, to);
+        let mut t = 0.0;
+        for _ in 0..max_iter {
+            let mut position: [f32; D] = [0.0; D];
+            for i in 0..D {
+                position[i] = from[i] + (to[i] - from[i]) * t;
+            }
+
+            let distance = self.signed_distance(&position);
+            if distance <= tol {
+                return true;
+            }
+            if length == 0.0 {
+                return false;
+            }
+
+            t += distance / length;
+            if t > 1.0 {
+                return self.signed_distance(to) <= tol;
+            }
+        }
+
+        // The segment grazes the surface
+        true
+    }
+
+    /// Returns the distance to the boundary of the obstacle, which is negative inside it.
+    ///
+    /// For `IntersectionObstacle` and `DifferenceObstacle`, and for `InflatedObstacle` around
+    /// them, the magnitude is a lower bound of the true distance.
+    pub fn signed_distance(&self, position: &[f32; D]) -> f32 {
+        match self {
+            Obstacle::RectObstacle { center, size } => {
+                let mut outside_distance = 0.0;
+                let mut inside_distance = f32::MIN;
+                for i in 0..D {
+                    let q = (position[i] - center[i]).abs() - size[i] / 2.0;
+                    outside_distance += q.max(0.0).powf(2.0);
+                    inside_distance = inside_distance.max(q);
+                }
+
+                outside_distance.powf(0.5) + inside_distance.min(0.0)
+            }
+            Obstacle::CircleObstacle { center, radius } => {
+                let mut distance = 0.0;
+                for i in 0..D {
+                    distance += (center[i] - position[i]).powf(2.0);
+                }
+                distance = distance.powf(0.5);
+
+                distance - radius
+            }
+            Obstacle::PolygonObstacle { vertices } => {
+                let point = (position[0], position[1]);
+                let distance = calc_distance_to_polygon_edges(vertices, point);
+                if is_inside_polygon(vertices, point) {
+                    -distance
+                } else {
+                    distance
+                }
+            }
+            Obstacle::PolytopeObstacle { normals, offsets } => {
+                let mut max_violation = f32::MIN;
+                for (normal, offset) in normals.iter().zip(offsets.iter()) {
+                    let norm = dot(normal, normal).powf(0.5);
+                    max_violation = max_violation.max((dot(normal, position) - offset) / norm);
+                }
+                if max_violation <= 0.0 {
+                    return max_violation;
+                }
+
+                let projection = project_onto_polytope(normals, offsets, position);
+                calc_distance(&projection, position)
+            }
+            Obstacle::OrientedRectObstacle {
+                center,
+                size,
+                rotation,
+            } => {
+                let local_rect = Obstacle::RectObstacle {
+                    center: [0.0; D],
+                    size: *size,
+                };
+                local_rect.signed_distance(&to_local_frame(center, rotation, position))
+            }
+            Obstacle::EllipsoidObstacle {
+                center,
+                radiuses,
+                rotation,
+            } => {
+                let local_position = to_local_frame(center, rotation, position);
+                calc_signed_distance_to_ellipsoid(radiuses, &local_position)
+            }
+            Obstacle::CapsuleObstacle { start, end, radius } => {
+                let closest_point = calc_closest_point_on_segment(start, end, position);
+                calc_distance(&closest_point, position) - radius
+            }
+            Obstacle::UnionObstacle { obstacles } => obstacles
+                .iter()
+                .map(|obstacle| obstacle.signed_distance(position))
+                .fold(f32::MAX, f32::min),
+            Obstacle::IntersectionObstacle { obstacles } => obstacles
+                .iter()
+                .map(|obstacle| obstacle.signed_distance(position))
+                .fold(f32::MIN, f32::max),
+            Obstacle::DifferenceObstacle { base, subtracted } => subtracted
+                .iter()
+                .map(|obstacle| -obstacle.signed_distance(position))
+                .fold(base.signed_distance(position), f32::max),
+            Obstacle::InflatedObstacle { obstacle, radius } => {
+                obstacle.signed_distance(position) - radius
+            }
         }
     }
+
+    /// Returns the lower and upper corners of an axis-aligned box enclosing the obstacle. Axes
+    /// along which the obstacle is unbounded span from `f32::MIN` to `f32::MAX`.
+    pub fn calc_aabb(&self) -> ([f32; D], [f32; D]) {
+        let mut low: [f32; D] = [f32::MIN; D];
+        let mut high: [f32; D] = [f32::MAX; D];
+        match self {
+            Obstacle::RectObstacle { center, size } => {
+                for i in 0..D {
+                    low[i] = center[i] - size[i] / 2.0;
+                    high[i] = center[i] + size[i] / 2.0;
+                }
+            }
+            Obstacle::CircleObstacle { center, radius } => {
+                for i in 0..D {
+                    low[i] = center[i] - radius;
+                    high[i] = center[i] + radius;
+                }
+            }
+            Obstacle::PolygonObstacle { vertices } => {
+                for i in 0..D.min(2) {
+                    low[i] = vertices.iter().map(|v| v[i]).fold(f32::MAX, f32::min);
+                    high[i] = vertices.iter().map(|v| v[i]).fold(f32::MIN, f32::max);
+                }
+            }
+            Obstacle::PolytopeObstacle { .. } => {}
+            Obstacle::OrientedRectObstacle {
+                center,
+                size,
+                rotation,
+            } => {
+                for i in 0..D {
+                    let half_extent = (0..D)
+                        .map(|j| rotation[i][j].abs() * size[j] / 2.0)
+                        .sum::<f32>();
+                    low[i] = center[i] - half_extent;
+                    high[i] = center[i] + half_extent;
+                }
+            }
+            Obstacle::EllipsoidObstacle {
+                center,
+                radiuses,
+                rotation,
+            } => {
+                for i in 0..D {
+                    let half_extent = (0..D)
+                        .map(|j| (rotation[i][j] * radiuses[j]).powf(2.0))
+                        .sum::<f32>()
+                        .powf(0.5);
+                    low[i] = center[i] - half_extent;
+                    high[i] = center[i] + half_extent;
+                }
+            }
+            Obstacle::CapsuleObstacle { start, end, radius } => {
+                for i in 0..D {
+                    low[i] = start[i].min(end[i]) - radius;
+                    high[i] = start[i].max(end[i]) + radius;
+                }
+            }
+            Obstacle::UnionObstacle { obstacles } => {
+                low = [f32::MAX; D];
+                high = [f32::MIN; D];
+                for obstacle in obstacles.iter() {
+                    let (obstacle_low, obstacle_high) = obstacle.calc_aabb();
+                    for i in 0..D {
+                        low[i] = low[i].min(obstacle_low[i]);
+                        high[i] = high[i].max(obstacle_high[i]);
+                    }
+                }
+            }
+            Obstacle::IntersectionObstacle { obstacles } => {
+                for obstacle in obstacles.iter() {
+                    let (obstacle_low, obstacle_high) = obstacle.calc_aabb();
+                    for i in 0..D {
+                        low[i] = low[i].max(obstacle_low[i]);
+                        high[i] = high[i].min(obstacle_high[i]);
+                    }
+                }
+            }
+            Obstacle::DifferenceObstacle { base, .. } => return base.calc_aabb(),
+            Obstacle::InflatedObstacle { obstacle, radius } => {
+                let (obstacle_low, obstacle_high) = obstacle.calc_aabb();
+                for i in 0..D {
+                    low[i] = (obstacle_low[i] - radius).max(f32::MIN);
+                    high[i] = (obstacle_high[i] + radius).min(f32::MAX);
+                }
+            }
+        }
+
+        (low, high)
+    }
+
+    /// Returns the Minkowski sum of the obstacle and a ball of `radius`.
+    pub fn inflate(&self, radius: f32) -> Obstacle<D> {
+        match self {
+            Obstacle::CircleObstacle {
+                center,
+                radius: circle_radius,
+            } => Obstacle::CircleObstacle {
+                center: *center,
+                radius: circle_radius + radius,
+            },
+            Obstacle::CapsuleObstacle {
+                start,
+                end,
+                radius: capsule_radius,
+            } => Obstacle::CapsuleObstacle {
+                start: *start,
+                end: *end,
+                radius: capsule_radius + radius,
+            },
+            Obstacle::UnionObstacle { obstacles } => Obstacle::UnionObstacle {
+                obstacles: obstacles
+                    .iter()
+                    .map(|obstacle| obstacle.inflate(radius))
+                    .collect(),
+            },
+            Obstacle::InflatedObstacle {
+                obstacle,
+                radius: inflated_radius,
+            } => Obstacle::InflatedObstacle {
+                obstacle: obstacle.clone(),
+                radius: inflated_radius + radius,
+            },
+            _ => Obstacle::InflatedObstacle {
+                obstacle: Box::new(self.clone()),
+                radius,
+            },
+        }
+    }
+
+    /// Returns the gradient of `signed_distance`, a unit vector wherever the distance is
+    /// differentiable. Polygons and ellipsoids, which have no closed form, use central differences
+    /// with a step scaled to the magnitude of `position`.
+    pub fn calc_gradient(&self, position: &[f32; D]) -> [f32; D] {
+        match self {
+            Obstacle::RectObstacle { center, size } => calc_rect_gradient(center, size, position),
+            Obstacle::CircleObstacle { center, .. } => normalize(&subtract(position, center)),
+            Obstacle::PolytopeObstacle { normals, offsets } => {
+                let mut max_violation = f32::MIN;
+                let mut gradient: [f32; D] = [0.0; D];
+                for (normal, offset) in normals.iter().zip(offsets.iter()) {
+                    let norm = dot(normal, normal).powf(0.5);
+                    let violation = (dot(normal, position) - offset) / norm;
+                    if violation > max_violation {
+                        max_violation = violation;
+                        gradient = normalize(normal);
+                    }
+                }
+                if max_violation <= 0.0 {
+                    return gradient;
+                }
+
+                let projection = project_onto_polytope(normals, offsets, position);
+                normalize(&subtract(position, &projection))
+            }
+            Obstacle::OrientedRectObstacle {
+                center,
+                size,
+                rotation,
+            } => {
+                let local_position = to_local_frame(center, rotation, position);
+                let local_gradient = calc_rect_gradient(&[0.0; D], size, &local_position);
+                to_world_direction(rotation, &local_gradient)
+            }
+            Obstacle::CapsuleObstacle { start, end, .. } => {
+                let closest_point = calc_closest_point_on_segment(start, end, position);
+                normalize(&subtract(position, &closest_point))
+            }
+            Obstacle::UnionObstacle { obstacles } => obstacles
+                .iter()
+                .min_by(|a, b| {
+                    a.signed_distance(position)
+                        .total_cmp(&b.signed_distance(position))
+                })
+                .map_or([0.0; D], |obstacle| obstacle.calc_gradient(position)),
+            Obstacle::IntersectionObstacle { obstacles } => obstacles
+                .iter()
+                .max_by(|a, b| {
+                    a.signed_distance(position)
+                        .total_cmp(&b.signed_distance(position))
+                })
+                .map_or([0.0; D], |obstacle| obstacle.calc_gradient(position)),
+            Obstacle::DifferenceObstacle { base, subtracted } => {
+                let mut max_distance = base.signed_distance(position);
+                let mut gradient = base.calc_gradient(position);
+                for obstacle in subtracted.iter() {
+                    let distance = -obstacle.signed_distance(position);
+                    if distance > max_distance {
+                        max_distance = distance;
+                        gradient = obstacle.calc_gradient(position).map(|x| -x);
+                    }
+                }
+                gradient
+            }
+            Obstacle::InflatedObstacle { obstacle, .. } => obstacle.calc_gradient(position),
+            Obstacle::PolygonObstacle { .. } | Obstacle::EllipsoidObstacle { .. } => {
+                self.calc_numerical_gradient(position)
+            }
+        }
+    }
+
+    /// Returns the normalized gradient of `signed_distance` by central differences. The step grows
+    /// with the magnitude of `position` so that it stays well above the precision of `f32`.
+    fn calc_numerical_gradient(&self, position: &[f32; D]) -> [f32; D] {
+        let scale = position
+            .iter()
+            .fold(1.0, |scale: f32, x| scale.max(x.abs()));
+        let eps = 1E-3 * scale;
+        let mut gradient: [f32; D] = [0.0; D];
+        for (i, value) in gradient.iter_mut().enumerate() {
+            let mut forward = *position;
+            let mut backward = *position;
+            forward[i] += eps;
+            backward[i] -= eps;
+            *value =
+                (self.signed_distance(&forward) - self.signed_distance(&backward)) / (2.0 * eps);
+        }
+
+        normalize(&gradient)
+    }
+}
+
+/// Returns the gradient of the signed distance to the box of `size` around `center`.
+fn calc_rect_gradient<const D: usize>(
+    center: &[f32; D],
+    size: &[f32; D],
+    position: &[f32; D],
+) -> [f32; D] {
+    let mut outside_direction: [f32; D] = [0.0; D];
+    let mut inside_direction: [f32; D] = [0.0; D];
+    let mut max_q = f32::MIN;
+    for i in 0..D {
+        let sign = if position[i] < center[i] { -1.0 } else { 1.0 };
+        let q = (position[i] - center[i]).abs() - size[i] / 2.0;
+        outside_direction[i] = sign * q.max(0.0);
+        if q > max_q {
+            max_q = q;
+            inside_direction = [0.0; D];
+            inside_direction[i] = sign;
+        }
+    }
+
+    if max_q > 0.0 {
+        normalize(&outside_direction)
+    } else {
+        inside_direction
+    }
+}
+
+fn dot<const D: usize>(a: &[f32; D], b: &[f32]) -> f32 {
+    (0..D).map(|i| a[i] * b[i]).sum()
+}
+
+fn subtract<const D: usize>(a: &[f32; D], b: &[f32; D]) -> [f32; D] {
+    let mut difference: [f32; D] = [0.0; D];
+    for (i, value) in difference.iter_mut().enumerate() {
+        *value = a[i] - b[i];
+    }
+    difference
+}
+
+/// Returns `vector` scaled to unit length, or the zero vector if `vector` is zero.
+fn normalize<const D: usize>(vector: &[f32; D]) -> [f32; D] {
+    let norm = dot(vector, vector).powf(0.5);
+    if norm == 0.0 {
+        return [0.0; D];
+    }
+    vector.map(|x| x / norm)
+}
+
+fn calc_distance<const D: usize>(a: &[f32; D], b: &[f32; D]) -> f32 {
+    (0..D)
+        .map(|i| (a[i] - b[i]).powf(2.0))
+        .sum::<f32>()
+        .powf(0.5)
+}
+
+fn calc_closest_point_on_segment<const D: usize>(
+    from: &[f32; D],
+    to: &[f32; D],
+    position: &[f32; D],
+) -> [f32; D] {
+    let mut direction: [f32; D] = [0.0; D];
+    let mut relative: [f32; D] = [0.0; D];
+    for i in 0..D {
+        direction[i] = to[i] - from[i];
+        relative[i] = position[i] - from[i];
+    }
+
+    let length_squared = dot(&direction, &direction);
+    let t = if length_squared > 0.0 {
+        (dot(&direction, &relative) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    let mut closest_point: [f32; D] = [0.0; D];
+    for i in 0..D {
+        closest_point[i] = from[i] + direction[i] * t;
+    }
+    closest_point
+}
+
+fn is_inside_polygon<const D: usize>(vertices: &[[f32; D]], point: (f32, f32)) -> bool {
+    // Even-odd rule with a ray cast along the first axis
+    let mut is_inside = false;
+    for k in 0..vertices.len() {
+        let (x_0, y_0) = (vertices[k][0], vertices[k][1]);
+        let next = (k + 1) % vertices.len();
+        let (x_1, y_1) = (vertices[next][0], vertices[next][1]);
+        if (y_0 > point.1) != (y_1 > point.1) {
+            let x = x_0 + (point.1 - y_0) * (x_1 - x_0) / (y_1 - y_0);
+            if point.0 < x {
+                is_inside = !is_inside;
+            }
+        }
+    }
+    is_inside
+}
+
+fn calc_distance_to_polygon_edges<const D: usize>(vertices: &[[f32; D]], point: (f32, f32)) -> f32 {
+    let mut min_distance = f32::MAX;
+    for k in 0..vertices.len() {
+        let next = (k + 1) % vertices.len();
+        let closest_point = calc_closest_point_on_segment(
+            &[vertices[k][0], vertices[k][1]],
+            &[vertices[next][0], vertices[next][1]],
+            &[point.0, point.1],
+        );
+        min_distance = min_distance.min(calc_distance(&closest_point, &[point.0, point.1]));
+    }
+    min_distance
+}
+
+fn is_intersecting_segments_2d(a: (f32, f32), b: (f32, f32), c: (f32, f32), d: (f32, f32)) -> bool {
+    let cross = |o: (f32, f32), p: (f32, f32), q: (f32, f32)| {
+        (p.0 - o.0) * (q.1 - o.1) - (p.1 - o.1) * (q.0 - o.0)
+    };
+    let is_on_segment = |o: (f32, f32), p: (f32, f32), q: (f32, f32)| {
+        q.0.min(o.0) <= p.0 && p.0 <= q.0.max(o.0) && q.1.min(o.1) <= p.1 && p.1 <= q.1.max(o.1)
+    };
+
+    let d_1 = cross(c, d, a);
+    let d_2 = cross(c, d, b);
+    let d_3 = cross(a, b, c);
+    let d_4 = cross(a, b, d);
+    if ((d_1 > 0.0 && d_2 < 0.0) || (d_1 < 0.0 && d_2 > 0.0))
+        && ((d_3 > 0.0 && d_4 < 0.0) || (d_3 < 0.0 && d_4 > 0.0))
+    {
+        return true;
+    }
+
+    (d_1 == 0.0 && is_on_segment(c, a, d))
+        || (d_2 == 0.0 && is_on_segment(c, b, d))
+        || (d_3 == 0.0 && is_on_segment(a, c, b))
+        || (d_4 == 0.0 && is_on_segment(a, d, b))
+}
+
+/// Returns `rotation^T (position - center)`.
+fn to_local_frame<const D: usize>(
+    center: &[f32; D],
+    rotation: &[[f32; D]; D],
+    position: &[f32],
+) -> [f32; D] {
+    let mut local_position: [f32; D] = [0.0; D];
+    for i in 0..D {
+        for j in 0..D {
+            local_position[i] += rotation[j][i] * (position[j] - center[j]);
+        }
+    }
+    local_position
+}
+
+/// Returns `rotation local_direction`, the inverse of `to_local_frame` for directions.
+fn to_world_direction<const D: usize>(
+    rotation: &[[f32; D]; D],
+    local_direction: &[f32; D],
+) -> [f32; D] {
+    let mut direction: [f32; D] = [0.0; D];
+    for (row, value) in rotation.iter().zip(direction.iter_mut()) {
+        *value = dot(row, local_direction);
+    }
+    direction
+}
+
+fn calc_distance_between_segments<const D: usize>(
+    p_0: &[f32; D],
+    p_1: &[f32; D],
+    q_0: &[f32; D],
+    q_1: &[f32; D],
+) -> f32 {
+    let mut d_p: [f32; D] = [0.0; D];
+    let mut d_q: [f32; D] = [0.0; D];
+    let mut r: [f32; D] = [0.0; D];
+    for i in 0..D {
+        d_p[i] = p_1[i] - p_0[i];
+        d_q[i] = q_1[i] - q_0[i];
+        r[i] = p_0[i] - q_0[i];
+    }
+
+    let a = dot(&d_p, &d_p);
+    let e = dot(&d_q, &d_q);
+    let f = dot(&d_q, &r);
+    if a == 0.0 {
+        return calc_distance(p_0, &calc_closest_point_on_segment(q_0, q_1, p_0));
+    }
+    if e == 0.0 {
+        return calc_distance(q_0, &calc_closest_point_on_segment(p_0, p_1, q_0));
+    }
+
+    // Closest points of the clamped parameters of both segments
+    let b = dot(&d_p, &d_q);
+    let c = dot(&d_p, &r);
+    let denominator = a * e - b * b;
+    let mut s = if denominator > 0.0 {
+        ((b * f - c * e) / denominator).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let mut t = (b * s + f) / e;
+    if t < 0.0 {
+        t = 0.0;
+        s = (-c / a).clamp(0.0, 1.0);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = ((b - c) / a).clamp(0.0, 1.0);
+    }
+
+    let mut p: [f32; D] = [0.0; D];
+    let mut q: [f32; D] = [0.0; D];
+    for i in 0..D {
+        p[i] = p_0[i] + d_p[i] * s;
+        q[i] = q_0[i] + d_q[i] * t;
+    }
+    calc_distance(&p, &q)
+}
+
+/// Returns the signed distance from `position` to the axis-aligned ellipsoid centered at the
+/// origin, following Eberly's bisection method for hyperellipsoids.
+fn calc_signed_distance_to_ellipsoid<const D: usize>(
+    radiuses: &[f32; D],
+    position: &[f32; D],
+) -> f32 {
+    // Work in the first orthant in double precision and avoid the degenerate zero coordinates
+    let e: Vec<f64> = radiuses.iter().map(|&r| r as f64).collect();
+    let y: Vec<f64> = (0..D)
+        .map(|i| (position[i].abs() as f64).max(1E-6 * e[i]))
+        .collect();
+
+    let g = |s: f64| -> f64 {
+        (0..D)
+            .map(|i| (e[i] * y[i] / (s + e[i] * e[i])).powf(2.0))
+            .sum::<f64>()
+            - 1.0
+    };
+
+    let e_min = e.iter().cloned().fold(f64::MAX, f64::min);
+    let mut lower = -e_min * e_min;
+    let mut upper = 1.0;
+    while g(upper) > 0.0 {
+        upper *= 2.0;
+    }
+    for _ in 0..200 {
+        let middle = (lower + upper) / 2.0;
+        if g(middle) > 0.0 {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+
+    let s = (lower + upper) / 2.0;
+    let distance = (0..D)
+        .map(|i| (y[i] - e[i] * e[i] * y[i] / (s + e[i] * e[i])).powf(2.0))
+        .sum::<f64>()
+        .powf(0.5) as f32;
+
+    let is_inside = (0..D)
+        .map(|i| (position[i] / radiuses[i]).powf(2.0))
+        .sum::<f32>()
+        <= 1.0;
+    if is_inside {
+        -distance
+    } else {
+        distance
+    }
+}
+
+/// Projects `position` onto the polytope with Dykstra's alternating projection algorithm.
+fn project_onto_polytope<const D: usize>(
+    normals: &[[f32; D]],
+    offsets: &[f32],
+    position: &[f32; D],
+) -> [f32; D] {
+    let max_iter = 1000;
+    let tol = 1E-6;
+
+    let mut projection = *position;
+    let mut corrections: Vec<[f32; D]> = vec![[0.0; D]; normals.len()];
+    for _ in 0..max_iter {
+        let previous = projection;
+        for (k, (normal, offset)) in normals.iter().zip(offsets.iter()).enumerate() {
+            let mut y: [f32; D] = [0.0; D];
+            for i in 0..D {
+                y[i] = projection[i] + corrections[k][i];
+            }
+
+            let violation = dot(normal, &y) - offset;
+            if violation > 0.0 {
+                let norm_squared = dot(normal, normal);
+                for i in 0..D {
+                    projection[i] = y[i] - violation / norm_squared * normal[i];
+                }
+            } else {
+                projection = y;
+            }
+
+            for i in 0..D {
+                corrections[k][i] = y[i] - projection[i];
+            }
+        }
+
+        if calc_distance(&previous, &projection) < tol {
+            break;
+        }
+    }
+
+    projection
 }
 
 impl<const D: usize> Clone for Obstacle<D> {
@@ -41,6 +943,50 @@ impl<const D: usize> Clone for Obstacle<D> {
                 center: center.clone(),
                 radius: *radius,
             },
+            Obstacle::PolygonObstacle { vertices } => Obstacle::PolygonObstacle {
+                vertices: vertices.clone(),
+            },
+            Obstacle::PolytopeObstacle { normals, offsets } => Obstacle::PolytopeObstacle {
+                normals: normals.clone(),
+                offsets: offsets.clone(),
+            },
+            Obstacle::OrientedRectObstacle {
+                center,
+                size,
+                rotation,
+            } => Obstacle::OrientedRectObstacle {
+                center: *center,
+                size: *size,
+                rotation: *rotation,
+            },
+            Obstacle::EllipsoidObstacle {
+                center,
+                radiuses,
+                rotation,
+            } => Obstacle::EllipsoidObstacle {
+                center: *center,
+                radiuses: *radiuses,
+                rotation: *rotation,
+            },
+            Obstacle::CapsuleObstacle { start, end, radius } => Obstacle::CapsuleObstacle {
+                start: *start,
+                end: *end,
+                radius: *radius,
+            },
+            Obstacle::UnionObstacle { obstacles } => Obstacle::UnionObstacle {
+                obstacles: obstacles.clone(),
+            },
+            Obstacle::IntersectionObstacle { obstacles } => Obstacle::IntersectionObstacle {
+                obstacles: obstacles.clone(),
+            },
+            Obstacle::DifferenceObstacle { base, subtracted } => Obstacle::DifferenceObstacle {
+                base: base.clone(),
+                subtracted: subtracted.clone(),
+            },
+            Obstacle::InflatedObstacle { obstacle, radius } => Obstacle::InflatedObstacle {
+                obstacle: obstacle.clone(),
+                radius: *radius,
+            },
         }
     }
 }
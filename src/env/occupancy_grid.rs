@@ -0,0 +1,271 @@
+use crate::env::AbstractEnv;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellState {
+    Free,
+    Occupied,
+    Unknown,
+}
+
+/// Grid of cells of size `resolution` whose first cell has its lower corner at `origin`.
+///
+/// Cells are stored with the first axis varying fastest. Positions outside the grid are treated
+/// as unknown.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OccupancyGrid<const D: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+    origin: [f32; D],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+    high: [f32; D],
+    resolution: f32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+    shape: [usize; D],
+    cells: Vec<CellState>,
+    pub is_unknown_occupied: bool,
+}
+
+impl<const D: usize> OccupancyGrid<D> {
+    pub fn new(origin: [f32; D], resolution: f32, shape: [usize; D]) -> Self {
+        let num_cells = shape.iter().product();
+        OccupancyGrid::from_cells(origin, resolution, shape, vec![CellState::Free; num_cells])
+    }
+
+    pub fn from_cells(
+        origin: [f32; D],
+        resolution: f32,
+        shape: [usize; D],
+        cells: Vec<CellState>,
+    ) -> Self {
+        assert_eq!(cells.len(), shape.iter().product::<usize>());
+
+        let mut high = origin;
+        for i in 0..D {
+            high[i] += shape[i] as f32 * resolution;
+        }
+
+        OccupancyGrid {
+            origin,
+            high,
+            resolution,
+            shape,
+            cells,
+            is_unknown_occupied: true,
+        }
+    }
+
+    pub fn get_origin(&self) -> &[f32; D] {
+        &self.origin
+    }
+
+    pub fn get_resolution(&self) -> f32 {
+        self.resolution
+    }
+
+    pub fn get_shape(&self) -> &[usize; D] {
+        &self.shape
+    }
+
+    pub fn get_cells(&self) -> &Vec<CellState> {
+        &self.cells
+    }
+
+    pub fn get_cell_index(&self, position: &[f32; D]) -> Option<[usize; D]> {
+        let mut index: [usize; D] = [0; D];
+        for i in 0..D {
+            let k = ((position[i] - self.origin[i]) / self.resolution).floor();
+            if k < 0.0 || k >= self.shape[i] as f32 {
+                return None;
+            }
+            index[i] = k as usize;
+        }
+        Some(index)
+    }
+
+    fn get_flat_index(&self, index: &[usize; D]) -> usize {
+        let mut flat_index = 0;
+        let mut stride = 1;
+        for (k, size) in index.iter().zip(self.shape.iter()) {
+            flat_index += k * stride;
+            stride *= size;
+        }
+        flat_index
+    }
+
+    pub fn get_cell(&self, index: &[usize; D]) -> CellState {
+        self.cells[self.get_flat_index(index)]
+    }
+
+    pub fn set_cell(&mut self, index: &[usize; D], state: CellState) {
+        let flat_index = self.get_flat_index(index);
+        self.cells[flat_index] = state;
+    }
+
+    pub fn get_cell_center(&self, index: &[usize; D]) -> [f32; D] {
+        let mut center: [f32; D] = [0.0; D];
+        for i in 0..D {
+            center[i] = self.origin[i] + (index[i] as f32 + 0.5) * self.resolution;
+        }
+        center
+    }
+
+    pub fn get_state(&self, position: &[f32; D]) -> CellState {
+        match self.get_cell_index(position) {
+            Some(index) => self.get_cell(&index),
+            None => CellState::Unknown,
+        }
+    }
+
+    /// Returns the grid in which every cell within `radius` of an occupied cell is occupied.
+    pub fn inflate(&self, radius: f32) -> OccupancyGrid<D> {
+        let num_kernel_cells = (radius / self.resolution).ceil() as i64 + 1;
+        let kernel_width = (2 * num_kernel_cells + 1) as usize;
+
+        // Offsets whose cell comes within radius of the center cell
+        let mut offsets: Vec<[i64; D]> = Vec::new();
+        for k in 0..kernel_width.pow(D as u32) {
+            let mut offset: [i64; D] = [0; D];
+            let mut rest = k;
+            for value in offset.iter_mut() {
+                *value = (rest % kernel_width) as i64 - num_kernel_cells;
+                rest /= kernel_width;
+            }
+
+            let gap = (0..D)
+                .map(|i| ((offset[i].abs() - 1).max(0) as f32 * self.resolution).powf(2.0))
+                .sum::<f32>()
+                .powf(0.5);
+            if gap <= radius {
+                offsets.push(offset);
+            }
+        }
+
+        let mut inflated_grid = self.clone();
+        for (flat_index, state) in self.cells.iter().enumerate() {
+            if *state != CellState::Occupied {
+                continue;
+            }
+
+            let index = self.get_index_from_flat_index(flat_index);
+            for offset in offsets.iter() {
+                let mut neighbor: [usize; D] = [0; D];
+                let mut is_in_grid = true;
+                for i in 0..D {
+                    let k = index[i] as i64 + offset[i];
+                    if k < 0 || k >= self.shape[i] as i64 {
+                        is_in_grid = false;
+                        break;
+                    }
+                    neighbor[i] = k as usize;
+                }
+                if is_in_grid {
+                    inflated_grid.set_cell(&neighbor, CellState::Occupied);
+                }
+            }
+        }
+
+        inflated_grid
+    }
+
+    fn get_index_from_flat_index(&self, flat_index: usize) -> [usize; D] {
+        let mut index: [usize; D] = [0; D];
+        let mut rest = flat_index;
+        for (k, size) in index.iter_mut().zip(self.shape.iter()) {
+            *k = rest % size;
+            rest /= size;
+        }
+        index
+    }
+
+    fn is_blocking(&self, state: CellState) -> bool {
+        match state {
+            CellState::Free => false,
+            CellState::Occupied => true,
+            CellState::Unknown => self.is_unknown_occupied,
+        }
+    }
+
+    pub fn is_inside_obstacle(&self, position: &[f32; D]) -> bool {
+        self.is_blocking(self.get_state(position))
+    }
+
+    /// Returns whether any cell crossed by the segment from `from` to `to` is blocking, walking
+    /// the cells with the Amanatides-Woo traversal.
+    pub fn is_intersecting_obstacle(&self, from: &[f32; D], to: &[f32; D]) -> bool {
+        let mut cell: [i64; D] = [0; D];
+        let mut steps: [i64; D] = [0; D];
+        let mut t_max: [f32; D] = [f32::MAX; D];
+        let mut t_delta: [f32; D] = [f32::MAX; D];
+        for i in 0..D {
+            let relative_position = (from[i] - self.origin[i]) / self.resolution;
+            cell[i] = relative_position.floor() as i64;
+
+            let direction = to[i] - from[i];
+            if direction > 0.0 {
+                steps[i] = 1;
+                t_max[i] = (cell[i] as f32 + 1.0 - relative_position) * self.resolution / direction;
+                t_delta[i] = self.resolution / direction;
+            } else if direction < 0.0 {
+                steps[i] = -1;
+                t_max[i] = (relative_position - cell[i] as f32) * self.resolution / -direction;
+                t_delta[i] = self.resolution / -direction;
+            }
+        }
+
+        loop {
+            let state = if (0..D).all(|i| 0 <= cell[i] && cell[i] < self.shape[i] as i64) {
+                let mut index: [usize; D] = [0; D];
+                for i in 0..D {
+                    index[i] = cell[i] as usize;
+                }
+                self.get_cell(&index)
+            } else {
+                CellState::Unknown
+            };
+            if self.is_blocking(state) {
+                return true;
+            }
+
+            // Step into the neighbor cell whose boundary is crossed first
+            let mut axis = 0;
+            for i in 1..D {
+                if t_max[i] < t_max[axis] {
+                    axis = i;
+                }
+            }
+            if t_max[axis] > 1.0 {
+                return false;
+            }
+            cell[axis] += steps[axis];
+            t_max[axis] += t_delta[axis];
+        }
+    }
+}
+
+impl<const D: usize> AbstractEnv<D> for OccupancyGrid<D> {
+    fn get_low(&self) -> &[f32; D] {
+        &self.origin
+    }
+    fn get_high(&self) -> &[f32; D] {
+        &self.high
+    }
+    fn is_inside_obstacle(&self, position: &[f32; D]) -> bool {
+        OccupancyGrid::is_inside_obstacle(self, position)
+    }
+    fn is_intersecting_obstacle(&self, from: &[f32; D], to: &[f32; D]) -> bool {
+        OccupancyGrid::is_intersecting_obstacle(self, from, to)
+    }
+}
+
+impl<const D: usize> Clone for OccupancyGrid<D> {
+    fn clone(&self) -> OccupancyGrid<D> {
+        OccupancyGrid {
+            origin: self.origin,
+            high: self.high,
+            resolution: self.resolution,
+            shape: self.shape,
+            cells: self.cells.clone(),
+            is_unknown_occupied: self.is_unknown_occupied,
+        }
+    }
+}
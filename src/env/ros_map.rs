@@ -0,0 +1,138 @@
+use crate::env::CellState;
+use crate::env::OccupancyGrid;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// How `map_server` turns the pixels of a map image into occupancy values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RosMapMode {
+    /// Occupied, free or unknown by the thresholds.
+    #[default]
+    Trinary,
+    /// Like `Trinary`, but occupancies between the thresholds are scaled linearly to 1 to 99.
+    Scale,
+    /// The pixel value is the occupancy in percent.
+    Raw,
+}
+
+/// Metadata of a ROS `map_server` map.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct RosMapMetadata {
+    pub image: String,
+    pub resolution: f32,
+    pub origin: [f32; 3],
+    #[serde(default = "default_occupied_thresh")]
+    pub occupied_thresh: f32,
+    #[serde(default = "default_free_thresh")]
+    pub free_thresh: f32,
+    #[serde(default, deserialize_with = "deserialize_negate")]
+    pub negate: bool,
+    #[serde(default)]
+    pub mode: RosMapMode,
+}
+
+fn default_occupied_thresh() -> f32 {
+    0.65
+}
+
+fn default_free_thresh() -> f32 {
+    0.196
+}
+
+/// Accepts `negate` both as the integer written by `map_saver` and as a boolean.
+fn deserialize_negate<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Negate {
+        Bool(bool),
+        Int(i64),
+    }
+
+    match serde::Deserialize::deserialize(deserializer)? {
+        Negate::Bool(negate) => Ok(negate),
+        Negate::Int(negate) => Ok(negate != 0),
+    }
+}
+
+impl RosMapMetadata {
+    /// Returns the occupancy value that `map_server` gives to a pixel of mean colour `color`:
+    /// 0 to 100 percent, or -1 for unknown. Values of `Raw` mode above 100 wrap around to negative
+    /// values as in the `int8` data of `map_server`.
+    pub fn calc_occupancy(&self, color: u8, alpha: u8) -> i8 {
+        let color = if self.negate { 255 - color } else { color };
+        if self.mode == RosMapMode::Raw {
+            return color as i8;
+        }
+
+        let occupancy = (255 - color) as f32 / 255.0;
+        if occupancy > self.occupied_thresh {
+            100
+        } else if occupancy < self.free_thresh {
+            0
+        } else if self.mode == RosMapMode::Trinary || alpha == 0 {
+            -1
+        } else {
+            let ratio = (occupancy - self.free_thresh) / (self.occupied_thresh - self.free_thresh);
+            (1.0 + 98.0 * ratio) as i8
+        }
+    }
+
+    /// Returns the state of a cell with the occupancy value of `calc_occupancy`. Values between 1
+    /// and 99 are compared with the thresholds in `Raw` mode and are unknown otherwise.
+    fn to_cell_state(&self, occupancy: i8) -> CellState {
+        match occupancy {
+            100 => CellState::Occupied,
+            0 => CellState::Free,
+            1..=99 if self.mode == RosMapMode::Raw => {
+                let occupancy = occupancy as f32 / 100.0;
+                if occupancy > self.occupied_thresh {
+                    CellState::Occupied
+                } else if occupancy < self.free_thresh {
+                    CellState::Free
+                } else {
+                    CellState::Unknown
+                }
+            }
+            _ => CellState::Unknown,
+        }
+    }
+}
+
+/// Parses the YAML file of a `map_server` map.
+pub fn parse_ros_map_yaml(text: &str) -> Result<RosMapMetadata, Box<dyn Error>> {
+    Ok(serde_yaml::from_str(text)?)
+}
+
+/// Loads a ROS `map_server` map from its YAML file. The image path is resolved relative to the
+/// YAML file, and the yaw of the origin is ignored as in `map_server`.
+pub fn load_ros_map<P: AsRef<Path>>(yaml_path: P) -> Result<OccupancyGrid<2>, Box<dyn Error>> {
+    let yaml_path = yaml_path.as_ref();
+    let metadata = parse_ros_map_yaml(&fs::read_to_string(yaml_path)?)?;
+    let image_path = match yaml_path.parent() {
+        Some(directory) => directory.join(&metadata.image),
+        None => Path::new(&metadata.image).to_path_buf(),
+    };
+
+    let image = image::open(image_path)?.to_rgba8();
+    let (width, height) = image.dimensions();
+
+    let mut cells = vec![CellState::Unknown; (width * height) as usize];
+    for (x, y, pixel) in image.enumerate_pixels() {
+        let [red, green, blue, alpha] = pixel.0;
+        let color = ((red as u32 + green as u32 + blue as u32) / 3) as u8;
+        let state = metadata.to_cell_state(metadata.calc_occupancy(color, alpha));
+
+        // The first image row is the top of the map
+        let j = height - 1 - y;
+        cells[(j * width + x) as usize] = state;
+    }
+
+    Ok(OccupancyGrid::from_cells(
+        [metadata.origin[0], metadata.origin[1]],
+        metadata.resolution,
+        [width as usize, height as usize],
+        cells,
+    ))
+}
@@ -1,3 +1,11 @@
+pub mod benchmark;
 pub mod env;
+pub mod path;
 pub mod planner;
 pub mod plot;
+#[cfg(feature = "serde")]
+pub mod scenario;
+pub mod trajectory;
+
+#[cfg(feature = "serde")]
+mod serde_array;
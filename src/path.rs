@@ -0,0 +1,11 @@
+mod metrics;
+mod path;
+mod smoothing;
+pub use crate::path::metrics::calc_average_clearance;
+pub use crate::path::metrics::calc_clearances;
+pub use crate::path::metrics::calc_max_curvature;
+pub use crate::path::metrics::calc_min_clearance;
+pub use crate::path::metrics::calc_smoothness;
+pub use crate::path::metrics::PathMetrics;
+pub use crate::path::path::Path;
+pub use crate::path::smoothing::PathSmoother;
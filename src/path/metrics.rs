@@ -0,0 +1,85 @@
+use crate::env::Env;
+use crate::path::Path;
+
+fn calc_clearance<const D: usize>(env: &Env<D>, position: &[f32; D]) -> f32 {
+    match env.distance_to_nearest_obstacle(position) {
+        Some((distance, _, _)) => distance.max(0.0),
+        None => f32::MAX,
+    }
+}
+
+/// Returns the distance to the nearest obstacle at points spaced at most `resolution` apart along
+/// the path. The clearance is zero inside obstacles.
+pub fn calc_clearances<const D: usize>(path: &Path<D>, env: &Env<D>, resolution: f32) -> Vec<f32> {
+    path.densify(resolution)
+        .waypoints
+        .iter()
+        .map(|position| calc_clearance(env, position))
+        .collect()
+}
+
+pub fn calc_min_clearance<const D: usize>(path: &Path<D>, env: &Env<D>, resolution: f32) -> f32 {
+    calc_clearances(path, env, resolution)
+        .into_iter()
+        .fold(f32::MAX, f32::min)
+}
+
+/// Returns the clearance averaged over the arc length of the path.
+pub fn calc_average_clearance<const D: usize>(
+    path: &Path<D>,
+    env: &Env<D>,
+    resolution: f32,
+) -> f32 {
+    let densified_path = path.densify(resolution);
+    let clearances: Vec<f32> = densified_path
+        .waypoints
+        .iter()
+        .map(|position| calc_clearance(env, position))
+        .collect();
+
+    let length = densified_path.get_length();
+    if length == 0.0 {
+        return clearances.first().copied().unwrap_or(f32::MAX);
+    }
+
+    // Integrate with the trapezoidal rule
+    let arc_lengths = densified_path.get_arc_lengths();
+    let mut integral = 0.0;
+    for k in 1..clearances.len() {
+        integral +=
+            (clearances[k - 1] + clearances[k]) / 2.0 * (arc_lengths[k] - arc_lengths[k - 1]);
+    }
+    integral / length
+}
+
+/// Returns the sum of the turning angles of the path in radians.
+pub fn calc_smoothness<const D: usize>(path: &Path<D>) -> f32 {
+    path.calc_turning_angles().iter().sum()
+}
+
+pub fn calc_max_curvature<const D: usize>(path: &Path<D>) -> f32 {
+    path.calc_curvatures().into_iter().fold(0.0, f32::max)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathMetrics {
+    pub length: f32,
+    pub min_clearance: f32,
+    pub average_clearance: f32,
+    pub smoothness: f32,
+    pub max_curvature: f32,
+    pub num_waypoints: usize,
+}
+
+impl PathMetrics {
+    pub fn new<const D: usize>(path: &Path<D>, env: &Env<D>, resolution: f32) -> Self {
+        PathMetrics {
+            length: path.get_length(),
+            min_clearance: calc_min_clearance(path, env, resolution),
+            average_clearance: calc_average_clearance(path, env, resolution),
+            smoothness: calc_smoothness(path),
+            max_curvature: calc_max_curvature(path),
+            num_waypoints: path.len(),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path<const D: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::vec_array"))]
+    pub waypoints: Vec<[f32; D]>,
+}
+
+fn calc_distance<const D: usize>(from: &[f32; D], to: &[f32; D]) -> f32 {
+    (0..D)
+        .map(|i| (to[i] - from[i]).powf(2.0))
+        .sum::<f32>()
+        .powf(0.5)
+}
+
+fn interpolate<const D: usize>(from: &[f32; D], to: &[f32; D], ratio: f32) -> [f32; D] {
+    let mut position: [f32; D] = [0.0; D];
+    for i in 0..D {
+        position[i] = from[i] + (to[i] - from[i]) * ratio;
+    }
+    position
+}
+
+impl<const D: usize> Path<D> {
+    pub fn new(waypoints: Vec<[f32; D]>) -> Self {
+        Path { waypoints }
+    }
+
+    pub fn len(&self) -> usize {
+        self.waypoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.waypoints.is_empty()
+    }
+
+    /// Returns the arc length from the first waypoint to each waypoint.
+    pub fn get_arc_lengths(&self) -> Vec<f32> {
+        let mut arc_lengths: Vec<f32> = Vec::with_capacity(self.waypoints.len());
+        for (k, waypoint) in self.waypoints.iter().enumerate() {
+            if k == 0 {
+                arc_lengths.push(0.0);
+            } else {
+                arc_lengths
+                    .push(arc_lengths[k - 1] + calc_distance(&self.waypoints[k - 1], waypoint));
+            }
+        }
+        arc_lengths
+    }
+
+    pub fn get_length(&self) -> f32 {
+        self.waypoints
+            .windows(2)
+            .map(|w| calc_distance(&w[0], &w[1]))
+            .sum()
+    }
+
+    /// Returns the position at arc length `s`, clamped to the ends of the path.
+    pub fn interpolate(&self, s: f32) -> [f32; D] {
+        if self.waypoints.is_empty() {
+            return [0.0; D];
+        }
+
+        let mut remaining = s.max(0.0);
+        for w in self.waypoints.windows(2) {
+            let distance = calc_distance(&w[0], &w[1]);
+            if remaining <= distance && distance > 0.0 {
+                return interpolate(&w[0], &w[1], remaining / distance);
+            }
+            remaining -= distance;
+        }
+
+        self.waypoints[self.waypoints.len() - 1]
+    }
+
+    /// Resamples the path at every `spacing` of arc length, always keeping both ends.
+    /// Paths with fewer than two waypoints are returned unchanged.
+    ///
+    /// Panics if `spacing` is not positive and finite.
+    pub fn resample_by_spacing(&self, spacing: f32) -> Path<D> {
+        assert!(
+            spacing > 0.0 && spacing.is_finite(),
+            "spacing must be positive and finite, got {}",
+            spacing
+        );
+        if self.waypoints.len() < 2 {
+            return Path::new(self.waypoints.clone());
+        }
+
+        let length = self.get_length();
+        let num_segments = (length / spacing).ceil().max(1.0) as usize;
+        let mut waypoints: Vec<[f32; D]> = (0..num_segments)
+            .map(|k| self.interpolate(k as f32 * spacing))
+            .collect();
+        waypoints.push(self.interpolate(length));
+        Path::new(waypoints)
+    }
+
+    /// Resamples the path to `count` waypoints equally spaced in arc length.
+    pub fn resample_by_count(&self, count: usize) -> Path<D> {
+        if count < 2 || self.waypoints.is_empty() {
+            return Path::new(self.waypoints.iter().take(count).cloned().collect());
+        }
+
+        let length = self.get_length();
+        let waypoints = (0..count)
+            .map(|k| self.interpolate(length * k as f32 / (count - 1) as f32))
+            .collect();
+        Path::new(waypoints)
+    }
+
+    /// Returns a copy in which no segment is longer than `max_spacing`.
+    ///
+    /// Panics if `max_spacing` is not positive and finite.
+    pub fn densify(&self, max_spacing: f32) -> Path<D> {
+        assert!(
+            max_spacing > 0.0 && max_spacing.is_finite(),
+            "max_spacing must be positive and finite, got {}",
+            max_spacing
+        );
+        let mut waypoints: Vec<[f32; D]> = Vec::new();
+        for w in self.waypoints.windows(2) {
+            let num_steps = (calc_distance(&w[0], &w[1]) / max_spacing).ceil().max(1.0) as usize;
+            for step in 0..num_steps {
+                waypoints.push(interpolate(&w[0], &w[1], step as f32 / num_steps as f32));
+            }
+        }
+        if let Some(last) = self.waypoints.last() {
+            waypoints.push(*last);
+        }
+        Path::new(waypoints)
+    }
+
+    /// Returns the turning angle at each waypoint in radians. The angle at both ends is zero.
+    pub fn calc_turning_angles(&self) -> Vec<f32> {
+        let n = self.waypoints.len();
+        let mut angles = vec![0.0; n];
+        for (angle, w) in angles.iter_mut().skip(1).zip(self.waypoints.windows(3)) {
+            let (prev, current, next) = (&w[0], &w[1], &w[2]);
+            let length_prev = calc_distance(prev, current);
+            let length_next = calc_distance(current, next);
+            if length_prev == 0.0 || length_next == 0.0 {
+                continue;
+            }
+
+            let cos_angle = (0..D)
+                .map(|i| (current[i] - prev[i]) * (next[i] - current[i]))
+                .sum::<f32>()
+                / (length_prev * length_next);
+            *angle = cos_angle.clamp(-1.0, 1.0).acos();
+        }
+        angles
+    }
+
+    /// Returns the discrete curvature at each waypoint, that is the turning angle divided by the
+    /// mean length of the adjacent segments. The curvature at both ends is zero.
+    pub fn calc_curvatures(&self) -> Vec<f32> {
+        let angles = self.calc_turning_angles();
+        let n = self.waypoints.len();
+        let mut curvatures = vec![0.0; n];
+        for k in 1..n.saturating_sub(1) {
+            let mean_length = (calc_distance(&self.waypoints[k - 1], &self.waypoints[k])
+                + calc_distance(&self.waypoints[k], &self.waypoints[k + 1]))
+                / 2.0;
+            if mean_length > 0.0 {
+                curvatures[k] = angles[k] / mean_length;
+            }
+        }
+        curvatures
+    }
+
+    /// Returns the closest point on the path to `position` and its arc length.
+    pub fn calc_closest_point(&self, position: &[f32; D]) -> Option<([f32; D], f32)> {
+        if self.waypoints.len() == 1 {
+            return Some((self.waypoints[0], 0.0));
+        }
+
+        let mut closest: Option<([f32; D], f32)> = None;
+        let mut min_distance = f32::MAX;
+        let mut s = 0.0;
+        for w in self.waypoints.windows(2) {
+            let length = calc_distance(&w[0], &w[1]);
+            let ratio = if length > 0.0 {
+                ((0..D)
+                    .map(|i| (position[i] - w[0][i]) * (w[1][i] - w[0][i]))
+                    .sum::<f32>()
+                    / length.powf(2.0))
+                .clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+
+            let point = interpolate(&w[0], &w[1], ratio);
+            let distance = calc_distance(&point, position);
+            if distance < min_distance {
+                min_distance = distance;
+                closest = Some((point, s + ratio * length));
+            }
+            s += length;
+        }
+
+        closest
+    }
+}
+
+impl<const D: usize> From<Vec<[f32; D]>> for Path<D> {
+    fn from(waypoints: Vec<[f32; D]>) -> Self {
+        Path::new(waypoints)
+    }
+}
+
+impl<const D: usize> Clone for Path<D> {
+    fn clone(&self) -> Path<D> {
+        Path {
+            waypoints: self.waypoints.clone(),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::planner::rng;
+use rand::prelude::*;
+
+/// Returns whether a position is free to pass through.
+type PositionChecker<const D: usize> = Box<dyn Fn(&[f32; D]) -> bool>;
+
+pub struct PathSmoother<const D: usize> {
+    is_approved: PositionChecker<D>,
+    pub resolution: f32,
+    /// Number of shortcut attempts made by `smooth`.
+    pub num_shortcut_iter: usize,
+    /// Number of relaxation sweeps made by `smooth`.
+    pub num_relax_iter: usize,
+    /// Weight of the move towards the midpoint of the neighbors in the relaxation of `smooth`.
+    pub relax_weight: f32,
+}
+
+impl<const D: usize> PathSmoother<D> {
+    pub fn new(is_approved: PositionChecker<D>, resolution: f32) -> Self {
+        PathSmoother {
+            is_approved,
+            resolution,
+            num_shortcut_iter: 100,
+            num_relax_iter: 50,
+            relax_weight: 0.5,
+        }
+    }
+
+    pub fn is_segment_approved(&self, from: &[f32; D], to: &[f32; D]) -> bool {
+        let distance = (0..D)
+            .map(|i| (to[i] - from[i]).powf(2.0))
+            .sum::<f32>()
+            .powf(0.5);
+        let num_steps = (distance / self.resolution).ceil().max(1.0) as usize;
+
+        for step in 0..=num_steps {
+            let t = step as f32 / num_steps as f32;
+            let mut position: [f32; D] = [0.0; D];
+            for i in 0..D {
+                position[i] = from[i] + (to[i] - from[i]) * t;
+            }
+            if !(self.is_approved)(&position) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Connects two randomly chosen waypoints directly whenever the segment between them is approved.
+    pub fn shortcut(&self, path: &[[f32; D]], num_iter: usize) -> Vec<[f32; D]> {
+        let mut path = path.to_vec();
+        let mut rng = rng();
+
+        for _ in 0..num_iter {
+            if path.len() < 3 {
+                break;
+            }
+
+            let i = rng.gen_range(0..path.len() - 2);
+            let j = rng.gen_range(i + 2..path.len());
+            if self.is_segment_approved(&path[i], &path[j]) {
+                path.drain(i + 1..j);
+            }
+        }
+
+        path
+    }
+
+    /// Keeps only the waypoints needed to keep line of sight from the previous kept waypoint.
+    pub fn prune(&self, path: &[[f32; D]]) -> Vec<[f32; D]> {
+        if path.len() < 3 {
+            return path.to_vec();
+        }
+
+        let mut pruned_path = vec![path[0]];
+        let mut anchor_index = 0;
+        while anchor_index < path.len() - 1 {
+            // Find the farthest waypoint visible from the anchor
+            let mut next_index = anchor_index + 1;
+            for j in (anchor_index + 2..path.len()).rev() {
+                if self.is_segment_approved(&path[anchor_index], &path[j]) {
+                    next_index = j;
+                    break;
+                }
+            }
+
+            pruned_path.push(path[next_index]);
+            anchor_index = next_index;
+        }
+
+        pruned_path
+    }
+
+    /// Moves each inner waypoint towards the midpoint of its neighbors while the path stays approved.
+    pub fn relax(&self, path: &[[f32; D]], num_iter: usize, weight: f32) -> Vec<[f32; D]> {
+        let mut path = path.to_vec();
+
+        for _ in 0..num_iter {
+            for k in 1..path.len().saturating_sub(1) {
+                let mut position = path[k];
+                for i in 0..D {
+                    let midpoint = (path[k - 1][i] + path[k + 1][i]) / 2.0;
+                    position[i] += weight * (midpoint - path[k][i]);
+                }
+
+                if self.is_segment_approved(&path[k - 1], &position)
+                    && self.is_segment_approved(&position, &path[k + 1])
+                {
+                    path[k] = position;
+                }
+            }
+        }
+
+        path
+    }
+
+    /// Shortcuts, prunes and relaxes the path with the settings of the smoother.
+    pub fn smooth(&self, path: &[[f32; D]]) -> Vec<[f32; D]> {
+        let path = self.shortcut(path, self.num_shortcut_iter);
+        let path = self.prune(&path);
+        self.relax(&path, self.num_relax_iter, self.relax_weight)
+    }
+}
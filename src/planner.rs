@@ -2,8 +2,19 @@ mod node;
 mod rrt;
 mod rrtstar;
 mod informed_rrtstar;
+mod random;
+mod stats;
+mod tree_log;
+pub use crate::planner::node::wrap_angle;
 pub use crate::planner::node::Node;
 pub use crate::planner::rrt::AbstractRRT;
 pub use crate::planner::rrt::RRT;
 pub use crate::planner::rrtstar::RRTStar;
 pub use crate::planner::informed_rrtstar::InformedRRTStar;
+pub(crate) use crate::planner::random::rng;
+pub use crate::planner::random::set_seed;
+pub use crate::planner::stats::PlannerStats;
+pub use crate::planner::tree_log::Snapshots;
+pub use crate::planner::tree_log::TreeEvent;
+pub use crate::planner::tree_log::TreeLog;
+pub use crate::planner::tree_log::TreeSnapshot;
@@ -1,9 +1,16 @@
 use rand::prelude::*;
+use rand_distr::StandardNormal;
 use ndarray::prelude::*;
 use ndarray_linalg::Determinant;
 use ndarray_linalg::SVD;
 use crate::planner::AbstractRRT;
 use crate::planner::Node;
+use crate::planner::PlannerStats;
+use crate::planner::TreeEvent;
+use crate::planner::TreeLog;
+use crate::planner::rng;
+use std::cell::Cell;
+use std::time::Instant;
 
 pub struct InformedRRTStar<const D: usize> {
     pub start: [f32; D],
@@ -19,9 +26,18 @@ pub struct InformedRRTStar<const D: usize> {
     pub goal_sample_rate: f32,
     pub step_size: f32,
     pub max_iter: usize,
+    /// Planning stops after this many seconds even if `max_iter` is not reached.
+    pub max_time: Option<f32>,
+    /// Axes that are angles in radians within (-pi, pi], whose differences are wrapped.
+    pub angular_axes: [bool; D],
+    /// When set, the edges of the tree are also approved at positions spaced at most this far
+    /// apart, not only at the nodes.
+    pub edge_resolution: Option<f32>,
     goal_node_index: usize,
     is_logginge_enabled: bool,
-    pub log: Vec<Vec<Node<D>>>,
+    num_collision_checks: Cell<usize>,
+    pub log: TreeLog<D>,
+    pub stats: PlannerStats,
 }
 
 impl<const D: usize> InformedRRTStar<D> {
@@ -37,7 +53,7 @@ impl<const D: usize> InformedRRTStar<D> {
     ) -> Self {
         let mut center: [f32; D] = [0.0; D];
         for i in 0..D {
-            center[i] = (high[i] + low[i]) / 2.0;
+            center[i] = (start[i] + goal[i]) / 2.0;
         }
 
         let cost_min = (0..D).map(|i| (goal[i] - start[i]).powf(2.0)).sum::<f32>().powf(0.5);
@@ -58,9 +74,14 @@ impl<const D: usize> InformedRRTStar<D> {
             goal_sample_rate,
             step_size,
             max_iter,
+            max_time: None,
+            angular_axes: [false; D],
+            edge_resolution: None,
             goal_node_index: 0,
             is_logginge_enabled: false,
-            log: Vec::new(),
+            num_collision_checks: Cell::new(0),
+            log: TreeLog::new(),
+            stats: PlannerStats::default(),
         }
     }
 }
@@ -71,59 +92,127 @@ fn get_rotation_to_main_frame<const D: usize>(start: [f32; D], goal: [f32; D]) -
     let difference = start_node.calc_difference(&goal_node);
     let distance = start_node.calc_distance(&goal_node);
 
+    // Any rotation is valid when the ellipse degenerates to a ball
+    if distance == 0.0 {
+        return Array2::eye(D);
+    }
+
     let a_1 = Array1::from_iter((0..D).map(|i| difference[i] / distance)).into_shape((D,1)).unwrap();
     
     let mut e_1 = Array::zeros((1,D));
     e_1[[0,0]] = 1.0;
     let m: Array2<f32> = a_1.dot(&e_1);
 
+    // C = U diag(1, ..., 1, det(U) det(V)) V^T
     let (u, _s, vt) = m.svd(true, true).unwrap();
+    let u = u.unwrap();
+    let vt = vt.unwrap();
     let mut d: Array2<f32> = Array2::eye(D);
-    d[(D-2, D-2)] = u.clone().unwrap().det().unwrap();
-    d[(D-1, D-1)] = vt.clone().unwrap().t().det().unwrap();
+    d[(D-1, D-1)] = u.det().unwrap() * vt.det().unwrap();
 
-    let c = u.clone().unwrap().dot(&d).dot(&vt.clone().unwrap());
-    return c;
+    u.dot(&d).dot(&vt)
+}
+
+fn get_unit_ball_volume(dim: usize) -> f32 {
+    // V_0 = 1, V_1 = 2, V_n = 2 * pi / n * V_(n-2)
+    let (mut volume, mut n) = match dim % 2 {
+        0 => (1.0, 2),
+        _ => (2.0, 3),
+    };
+    while n <= dim {
+        volume *= 2.0 * std::f32::consts::PI / n as f32;
+        n += 2;
+    }
+    volume
 }
 
 impl<const D: usize> InformedRRTStar<D> {
+    /// Records the changes to the tree in `log` from now on, starting with the current nodes.
     pub fn enable_logging(&mut self) {
         self.is_logginge_enabled = true;
+        for node in self.nodes.clone() {
+            self.log_event(TreeEvent::NodeAdded {
+                position: node.position,
+                parent: node.parent,
+                cost: node.cost,
+            });
+        }
+    }
+
+    fn log_event(&mut self, event: TreeEvent<D>) {
+        if self.is_logginge_enabled {
+            self.log.push(event);
+        }
     }
 
     fn sample_from_unit_ball(&self) -> [f32; D] {
-        let mut rng = thread_rng();
+        let mut rng = rng();
 
-        loop {
-            let mut position: [f32; D] = [0.0; D];
-            for i in 0..D {
-                position[i] = rng.gen::<f32>() * 2.0 - 1.0;
-            }
-            let distance_from_origin = position.iter().map(|x| x.powf(2.0)).sum::<f32>().powf(0.5);
-            if distance_from_origin <= 1.0 {
-                return position;
-            }
+        // Normalize a standard normal vector to get a uniform direction
+        let mut position: [f32; D] = [0.0; D];
+        for x in position.iter_mut() {
+            *x = rng.sample(StandardNormal);
         }
+        let norm = position.iter().map(|x| x.powf(2.0)).sum::<f32>().powf(0.5);
+        if norm == 0.0 {
+            return position;
+        }
+
+        // Scale the radius so that the samples are uniform in volume
+        let radius = rng.gen::<f32>().powf(1.0 / D as f32);
+        for x in position.iter_mut() {
+            *x *= radius / norm;
+        }
+        position
     }
 
-    fn sample_from_informed_elipse(&self) -> Node<D> {
+    fn get_informed_elipse_radiuses(&self) -> Array1<f32> {
         let mut radiuses = Array::zeros(D);
         radiuses[0] = self.cost_max / 2.0;
         for i in 1..D {
-            radiuses[i] = (self.cost_max.powf(2.0) - self.cost_min.powf(2.0)).powf(0.5) / 2.0;
+            radiuses[i] = (self.cost_max.powf(2.0) - self.cost_min.powf(2.0)).max(0.0).powf(0.5) / 2.0;
+        }
+        radiuses
+    }
+
+    fn is_inside_bounds(&self, position: &[f32; D]) -> bool {
+        (0..D).all(|i| self.low[i] <= position[i] && position[i] <= self.high[i])
+    }
+
+    fn is_inside_informed_elipse(&self, position: &[f32; D]) -> bool {
+        let cost = self.calc_distance(&self.start, position) + self.calc_distance(position, &self.goal);
+        cost <= self.cost_max
+    }
+
+    fn sample_from_informed_elipse(&self) -> Node<D> {
+        let radiuses = self.get_informed_elipse_radiuses();
+
+        // Sample from whichever of the ellipse and the bounds is smaller,
+        // and reject the samples outside the other one
+        let elipse_volume = get_unit_ball_volume(D) * radiuses.iter().product::<f32>();
+        let bounds_volume = (0..D).map(|i| self.high[i] - self.low[i]).product::<f32>();
+        if bounds_volume <= elipse_volume {
+            loop {
+                let node = self.sample();
+                if self.is_inside_informed_elipse(&node.position) {
+                    return node;
+                }
+            }
         }
-        let l = Array::from_diag(&radiuses);
 
+        let l = Array::from_diag(&radiuses);
         let _center = arr1(&self.center);
         loop {
             let mut position = arr1(&self.sample_from_unit_ball());
-            position = self.rotation_matrix.dot(&l).dot(&position) + _center;
+            position = self.rotation_matrix.dot(&l).dot(&position) + &_center;
 
             let mut _position: [f32; D] = [0.0; D];
             for i in 0..D {
                 _position[i] = position[i];
             }
-            return Node::new(_position);
+            if self.is_inside_bounds(&_position) {
+                return Node::new(_position);
+            }
         }
     }
 
@@ -137,32 +226,55 @@ impl<const D: usize> InformedRRTStar<D> {
         // Add new_node to nodes
         let parent_node = &self.nodes[parent_node_index];
         new_node.parent = Some(parent_node_index);
-        new_node.cost = parent_node.cost + parent_node.calc_distance(&new_node);
+        new_node.cost =
+            parent_node.cost + self.calc_distance(&parent_node.position, &new_node.position);
+        self.log_event(TreeEvent::NodeAdded {
+            position: new_node.position,
+            parent: new_node.parent,
+            cost: new_node.cost,
+        });
         self.nodes.push(new_node);
     }
 
     pub fn get_parent_node_index_minimize_cost(&self, new_node: &Node<D>) -> usize {
+        let candidate_indices = self.get_parent_candidate_indices(new_node);
+        self.choose_parent_node_index(new_node, &candidate_indices)
+    }
+
+    /// Returns the indices of the nodes within the step size of `new_node`, in increasing order.
+    fn get_parent_candidate_indices(&self, new_node: &Node<D>) -> Vec<usize> {
         let tol = 1E-5;
+        (0..self.nodes.len())
+            .filter(|&i| {
+                self.calc_distance(&self.nodes[i].position, &new_node.position)
+                    <= self.step_size + tol
+            })
+            .collect()
+    }
+
+    /// Returns the candidate through which `new_node` is reached at the lowest cost over an
+    /// approved edge, or the root if there is none.
+    fn choose_parent_node_index(&self, new_node: &Node<D>, candidate_indices: &[usize]) -> usize {
         let mut parent_node_index: usize = 0;
         let mut minimum_cost = f32::MAX;
 
-        for i in 0..self.nodes.len() {
+        for &i in candidate_indices.iter() {
             let node = &self.nodes[i];
-            let distance = node.calc_distance(&new_node);
-            let new_cost = node.cost + distance;
-            if distance <= self.step_size + tol && new_cost < minimum_cost {
+            let new_cost = node.cost + self.calc_distance(&node.position, &new_node.position);
+            if new_cost < minimum_cost && self.is_edge_approved(&node.position, &new_node.position)
+            {
                 minimum_cost = new_cost;
                 parent_node_index = i;
             }
         }
 
-        return parent_node_index;
+        parent_node_index
     }
 
     pub fn get_near_node_indices(&self, node: &Node<D>) -> Vec<usize> {
         let mut near_node_indices: Vec<usize> = Vec::new();
         for i in 0..self.nodes.len() {
-            if self.nodes[i].calc_distance(&node) <= self.step_size {
+            if self.calc_distance(&self.nodes[i].position, &node.position) <= self.step_size {
                 near_node_indices.push(i);
             }
         }
@@ -178,9 +290,15 @@ impl<const D: usize> InformedRRTStar<D> {
 
     pub fn rewire_near_nodes(&mut self, near_node_indices: Vec<usize>, new_node_index: usize) {
         for near_node_index in near_node_indices {
-            let new_cost = self.nodes[new_node_index].cost
-                + self.nodes[new_node_index].calc_distance(&self.nodes[near_node_index]);
-            if new_cost < self.nodes[near_node_index].cost {
+            let (new_position, near_position) = (
+                self.nodes[new_node_index].position,
+                self.nodes[near_node_index].position,
+            );
+            let new_cost =
+                self.nodes[new_node_index].cost + self.calc_distance(&new_position, &near_position);
+            if new_cost < self.nodes[near_node_index].cost
+                && self.is_edge_approved(&new_position, &near_position)
+            {
                 // Delete near_node from children of the current parent node of the near node
                 let near_node_parent = self.nodes[near_node_index].parent.unwrap();
                 self.nodes[near_node_parent]
@@ -195,20 +313,39 @@ impl<const D: usize> InformedRRTStar<D> {
 
                 let diff_cost = new_cost - self.nodes[near_node_index].cost;
                 self.update_costs(near_node_index, diff_cost);
+                self.stats.num_rewires += 1;
+                self.log_event(TreeEvent::ParentChanged {
+                    node: near_node_index,
+                    parent: new_node_index,
+                });
+                self.log_event(TreeEvent::CostUpdated {
+                    node: near_node_index,
+                    cost: new_cost,
+                });
             }
         }
     }
 
     pub fn plan(&mut self) -> Vec<[f32; D]> {
+        let start_time = Instant::now();
+        self.stats = PlannerStats::default();
+        self.num_collision_checks.set(0);
         let mut is_goaled = false;
 
         for _ in 0..self.max_iter {
+            if let Some(max_time) = self.max_time {
+                if max_time <= start_time.elapsed().as_secs_f32() {
+                    break;
+                }
+            }
+            self.stats.num_iterations += 1;
+
             // Sample a node
             let mut new_node;
             if is_goaled {
                 new_node = self.sample_from_informed_elipse();
             } else {
-                if thread_rng().gen::<f32>() < self.goal_sample_rate {
+                if rng().gen::<f32>() < self.goal_sample_rate {
                     new_node = Node::new(self.goal);
                 } else {
                     new_node = self.sample();
@@ -216,20 +353,31 @@ impl<const D: usize> InformedRRTStar<D> {
             }
 
             // Get the nearest node
+            let neighbor_search_start_time = Instant::now();
             let nearest_node_index = self.get_nearest_node_index(&new_node);
+            self.stats.nearest_neighbor_time += neighbor_search_start_time.elapsed().as_secs_f32();
             let nearest_node = &self.nodes[nearest_node_index];
 
-            let distance_from_nearest_node = nearest_node.calc_distance(&new_node);
+            let distance_from_nearest_node =
+                self.calc_distance(&nearest_node.position, &new_node.position);
             if self.step_size < distance_from_nearest_node {
                 new_node = self.get_extended_node(nearest_node, &new_node);
             }
 
-            if !(self.is_approved)(&new_node.position) {
+            if !self.is_position_approved(&new_node.position) {
+                self.stats.num_rejected_samples += 1;
+                continue;
+            }
+            if !self.is_edge_approved(&nearest_node.position, &new_node.position) {
+                self.stats.num_rejected_edges += 1;
                 continue;
             }
 
             // Add the new node to the tree
-            let parent_node_index = self.get_parent_node_index_minimize_cost(&new_node);
+            let neighbor_search_start_time = Instant::now();
+            let candidate_indices = self.get_parent_candidate_indices(&new_node);
+            self.stats.nearest_neighbor_time += neighbor_search_start_time.elapsed().as_secs_f32();
+            let parent_node_index = self.choose_parent_node_index(&new_node, &candidate_indices);
             self.add_node(new_node, parent_node_index);
 
             // Add the new node to the tree
@@ -244,18 +392,26 @@ impl<const D: usize> InformedRRTStar<D> {
             // Rewire near nodes
             let new_node_index = self.nodes.len() - 1;
             let new_node = &self.nodes[new_node_index];
+            let neighbor_search_start_time = Instant::now();
             let near_node_indices = self.get_near_node_indices(new_node);
+            self.stats.nearest_neighbor_time += neighbor_search_start_time.elapsed().as_secs_f32();
             self.rewire_near_nodes(near_node_indices, new_node_index);
 
             if is_goaled {
                 self.cost_max = self.nodes[self.goal_node_index].cost;
+                if self.stats.get_best_cost() != Some(self.cost_max) {
+                    self.log_event(TreeEvent::SolutionImproved {
+                        goal_node: self.goal_node_index,
+                        cost: self.cost_max,
+                    });
+                }
+                self.stats.record_cost(start_time.elapsed().as_secs_f32(), self.cost_max);
             }
 
-            if self.is_logginge_enabled {
-                self.log.push(self.nodes.clone());
-            }
+            self.log_event(TreeEvent::Iteration);
         }
 
+        self.stats.num_collision_checks = self.num_collision_checks.get();
         return self.extract_path();
     }
 }
@@ -279,4 +435,30 @@ impl<const D: usize> AbstractRRT<D> for InformedRRTStar<D> {
     fn get_nodes(&self) -> &Vec<Node<D>> {
         &self.nodes
     }
+    fn get_stats(&self) -> &PlannerStats {
+        &self.stats
+    }
+    fn get_angular_axes(&self) -> &[bool; D] {
+        &self.angular_axes
+    }
+    fn get_edge_resolution(&self) -> Option<f32> {
+        self.edge_resolution
+    }
+    fn set_max_time(&mut self, max_time: Option<f32>) {
+        self.max_time = max_time;
+    }
+    fn get_log(&self) -> &TreeLog<D> {
+        &self.log
+    }
+    fn enable_logging(&mut self) {
+        InformedRRTStar::enable_logging(self)
+    }
+    fn is_position_approved(&self, position: &[f32; D]) -> bool {
+        self.num_collision_checks
+            .set(self.num_collision_checks.get() + 1);
+        (self.is_approved)(position)
+    }
+    fn plan(&mut self) -> Vec<[f32; D]> {
+        InformedRRTStar::plan(self)
+    }
 }
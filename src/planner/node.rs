@@ -1,6 +1,19 @@
 use std::collections::HashSet;
+use std::f32::consts::PI;
 
+/// Returns `angle` wrapped to (-pi, pi].
+pub fn wrap_angle(angle: f32) -> f32 {
+    let wrapped_angle = (angle + PI).rem_euclid(2.0 * PI) - PI;
+    if wrapped_angle == -PI {
+        PI
+    } else {
+        wrapped_angle
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node<const D: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
     pub position: [f32; D],
     pub parent: Option<usize>,
     pub children: HashSet<usize>,
@@ -1,5 +1,12 @@
+use crate::planner::node::wrap_angle;
 use crate::planner::node::Node;
+use crate::planner::rng;
+use crate::planner::PlannerStats;
+use crate::planner::TreeEvent;
+use crate::planner::TreeLog;
 use rand::prelude::*;
+use std::cell::Cell;
+use std::time::Instant;
 
 pub trait AbstractRRT<const D: usize> {
     fn get_low(&self) -> &[f32; D];
@@ -8,12 +15,72 @@ pub trait AbstractRRT<const D: usize> {
     fn get_nodes(&self) -> &Vec<Node<D>>;
     fn get_step_size(&self) -> f32;
     fn get_goal_node_index(&self) -> usize;
+    fn get_log(&self) -> &TreeLog<D>;
+    fn get_stats(&self) -> &PlannerStats;
+    fn get_angular_axes(&self) -> &[bool; D];
+    fn get_edge_resolution(&self) -> Option<f32>;
+    fn set_max_time(&mut self, max_time: Option<f32>);
+    fn enable_logging(&mut self);
+    fn is_position_approved(&self, position: &[f32; D]) -> bool;
+    fn plan(&mut self) -> Vec<[f32; D]>;
+
+    /// Returns the difference from `from` to `to`, going the shorter way round on angular axes.
+    fn calc_difference(&self, from: &[f32; D], to: &[f32; D]) -> [f32; D] {
+        let mut difference: [f32; D] = [0.0; D];
+        for (i, value) in difference.iter_mut().enumerate() {
+            *value = to[i] - from[i];
+            if self.get_angular_axes()[i] {
+                *value = wrap_angle(*value);
+            }
+        }
+
+        difference
+    }
+
+    fn calc_distance(&self, from: &[f32; D], to: &[f32; D]) -> f32 {
+        self.calc_difference(from, to)
+            .iter()
+            .map(|x| x.powf(2.0))
+            .sum::<f32>()
+            .powf(0.5)
+    }
+
+    /// Returns the position at `ratio` of the way from `from` to `to`, with angular axes wrapped
+    /// to (-pi, pi].
+    fn interpolate(&self, from: &[f32; D], to: &[f32; D], ratio: f32) -> [f32; D] {
+        let difference = self.calc_difference(from, to);
+        let mut position = *from;
+        for (i, value) in position.iter_mut().enumerate() {
+            *value += difference[i] * ratio;
+            if self.get_angular_axes()[i] {
+                *value = wrap_angle(*value);
+            }
+        }
+
+        position
+    }
+
+    /// Returns whether the positions strictly between `from` and `to`, spaced at most the edge
+    /// resolution apart, are approved. The ends are not checked, and without an edge resolution
+    /// every edge is approved.
+    fn is_edge_approved(&self, from: &[f32; D], to: &[f32; D]) -> bool {
+        let resolution = match self.get_edge_resolution() {
+            Some(resolution) => resolution,
+            None => return true,
+        };
+
+        let num_steps = (self.calc_distance(from, to) / resolution).ceil() as usize;
+        (1..num_steps).all(|step| {
+            let position = self.interpolate(from, to, step as f32 / num_steps as f32);
+            self.is_position_approved(&position)
+        })
+    }
 
     fn sample(&self) -> Node<D> {
         let low = self.get_low();
         let high = self.get_high();
 
-        let mut rng = thread_rng();
+        let mut rng = rng();
         let mut position: [f32; D] = [0.0; D];
         for i in 0..D {
             position[i] = rng.gen_range(low[i]..high[i]);
@@ -27,7 +94,7 @@ pub trait AbstractRRT<const D: usize> {
         let mut min_distance = f32::MAX;
 
         for (i, node) in self.get_nodes().iter().enumerate() {
-            let distance = node.calc_distance(new_node);
+            let distance = self.calc_distance(&node.position, &new_node.position);
             if distance < min_distance {
                 min_distance = distance;
                 nearest_node_index = i;
@@ -38,20 +105,16 @@ pub trait AbstractRRT<const D: usize> {
     }
 
     fn get_extended_node(&self, nearest_node: &Node<D>, new_node: &Node<D>) -> Node<D> {
-        let difference = nearest_node.calc_difference(&new_node);
-        let distance = nearest_node.calc_distance(&new_node);
-
-        let mut new_position = nearest_node.position.clone();
-        for i in 0..D {
-            new_position[i] += difference[i] * (self.get_step_size() / distance);
-        }
+        let distance = self.calc_distance(&nearest_node.position, &new_node.position);
+        let ratio = self.get_step_size() / distance;
 
-        Node::new(new_position)
+        Node::new(self.interpolate(&nearest_node.position, &new_node.position, ratio))
     }
 
     fn is_near_goal(&self, node: &Node<D>) -> bool {
-        let distance_from_goal = node.calc_distance(&Node::new((*self.get_goal()).clone()));
-        return distance_from_goal <= self.get_step_size();
+        let distance_from_goal = self.calc_distance(&node.position, self.get_goal());
+        distance_from_goal <= self.get_step_size()
+            && self.is_edge_approved(&node.position, self.get_goal())
     }
 
     fn extract_path(&self) -> Vec<[f32; D]> {
@@ -82,9 +145,18 @@ pub struct RRT<const D: usize> {
     pub goal_sample_rate: f32,
     pub step_size: f32,
     pub max_iter: usize,
+    /// Planning stops after this many seconds even if `max_iter` is not reached.
+    pub max_time: Option<f32>,
+    /// Axes that are angles in radians within (-pi, pi], whose differences are wrapped.
+    pub angular_axes: [bool; D],
+    /// When set, the edges of the tree are also approved at positions spaced at most this far
+    /// apart, not only at the nodes.
+    pub edge_resolution: Option<f32>,
     goal_node_index: usize,
     is_logginge_enabled: bool,
-    pub log: Vec<Vec<Node<D>>>,
+    num_collision_checks: Cell<usize>,
+    pub log: TreeLog<D>,
+    pub stats: PlannerStats,
 }
 
 impl<const D: usize> RRT<D> {
@@ -108,67 +180,125 @@ impl<const D: usize> RRT<D> {
             goal_sample_rate,
             step_size,
             max_iter,
+            max_time: None,
+            angular_axes: [false; D],
+            edge_resolution: None,
             goal_node_index: 0,
             is_logginge_enabled: false,
-            log: Vec::new(),
+            num_collision_checks: Cell::new(0),
+            log: TreeLog::new(),
+            stats: PlannerStats::default(),
         }
     }
 }
 
 impl<const D: usize> RRT<D> {
+    /// Records the changes to the tree in `log` from now on, starting with the current nodes.
     pub fn enable_logging(&mut self) {
         self.is_logginge_enabled = true;
+        for node in self.nodes.clone() {
+            self.log_event(TreeEvent::NodeAdded {
+                position: node.position,
+                parent: node.parent,
+                cost: node.cost,
+            });
+        }
+    }
+
+    fn log_event(&mut self, event: TreeEvent<D>) {
+        if self.is_logginge_enabled {
+            self.log.push(event);
+        }
+    }
+
+    fn push_node(&mut self, node: Node<D>) {
+        self.log_event(TreeEvent::NodeAdded {
+            position: node.position,
+            parent: node.parent,
+            cost: node.cost,
+        });
+        self.nodes.push(node);
     }
 
     pub fn plan(&mut self) -> Vec<[f32; D]> {
+        let start_time = Instant::now();
+        self.stats = PlannerStats::default();
+        self.num_collision_checks.set(0);
         let mut is_goaled = false;
 
         for _ in 0..self.max_iter {
+            if let Some(max_time) = self.max_time {
+                if max_time <= start_time.elapsed().as_secs_f32() {
+                    break;
+                }
+            }
+            self.stats.num_iterations += 1;
+
             // Sample a node
             let mut new_node;
-            if thread_rng().gen::<f32>() < self.goal_sample_rate {
+            if rng().gen::<f32>() < self.goal_sample_rate {
                 new_node = Node::new(self.goal);
             } else {
                 new_node = self.sample();
             }
 
             // Get the nearest node
+            let neighbor_search_start_time = Instant::now();
             let nearest_node_index = self.get_nearest_node_index(&new_node);
+            self.stats.nearest_neighbor_time += neighbor_search_start_time.elapsed().as_secs_f32();
             let nearest_node = &self.nodes[nearest_node_index];
 
-            let distance_from_nearest_node = nearest_node.calc_distance(&new_node);
+            let distance_from_nearest_node =
+                self.calc_distance(&nearest_node.position, &new_node.position);
             if self.step_size < distance_from_nearest_node {
                 new_node = self.get_extended_node(nearest_node, &new_node);
             }
 
-            if !(self.is_approved)(&new_node.position) {
+            if !self.is_position_approved(&new_node.position) {
+                self.stats.num_rejected_samples += 1;
+                continue;
+            }
+            if !self.is_edge_approved(&nearest_node.position, &new_node.position) {
+                self.stats.num_rejected_edges += 1;
                 continue;
             }
 
             // Add the new node to the tree
             new_node.parent = Some(nearest_node_index);
+            new_node.cost =
+                nearest_node.cost + self.calc_distance(&nearest_node.position, &new_node.position);
             if self.is_near_goal(&new_node) {
-                self.nodes.push(new_node);
+                let mut goal_node = Node::new(self.goal);
+                goal_node.cost = new_node.cost + self.calc_distance(&new_node.position, &self.goal);
+                self.push_node(new_node);
 
                 let new_node_index = self.nodes.len() - 1;
-                let mut goal_node = Node::new(self.goal);
                 goal_node.parent = Some(new_node_index);
-                self.nodes.push(goal_node);
+                self.push_node(goal_node);
                 is_goaled = true
             } else {
-                self.nodes.push(new_node);
+                self.push_node(new_node);
             }
 
-            if self.is_logginge_enabled {
-                self.log.push(self.nodes.clone());
+            if is_goaled {
+                self.goal_node_index = self.nodes.len() - 1;
+                let cost = self.nodes[self.goal_node_index].cost;
+                self.log_event(TreeEvent::SolutionImproved {
+                    goal_node: self.goal_node_index,
+                    cost,
+                });
+                self.stats
+                    .record_cost(start_time.elapsed().as_secs_f32(), cost);
             }
+            self.log_event(TreeEvent::Iteration);
 
             if is_goaled {
-                self.goal_node_index = self.nodes.len() - 1;
+                self.stats.num_collision_checks = self.num_collision_checks.get();
                 return self.extract_path();
             }
         }
 
+        self.stats.num_collision_checks = self.num_collision_checks.get();
         return Vec::new();
     }
 }
@@ -192,4 +322,30 @@ impl<const D: usize> AbstractRRT<D> for RRT<D> {
     fn get_nodes(&self) -> &Vec<Node<D>> {
         &self.nodes
     }
+    fn get_stats(&self) -> &PlannerStats {
+        &self.stats
+    }
+    fn get_angular_axes(&self) -> &[bool; D] {
+        &self.angular_axes
+    }
+    fn get_edge_resolution(&self) -> Option<f32> {
+        self.edge_resolution
+    }
+    fn set_max_time(&mut self, max_time: Option<f32>) {
+        self.max_time = max_time;
+    }
+    fn get_log(&self) -> &TreeLog<D> {
+        &self.log
+    }
+    fn enable_logging(&mut self) {
+        RRT::enable_logging(self)
+    }
+    fn is_position_approved(&self, position: &[f32; D]) -> bool {
+        self.num_collision_checks
+            .set(self.num_collision_checks.get() + 1);
+        (self.is_approved)(position)
+    }
+    fn plan(&mut self) -> Vec<[f32; D]> {
+        RRT::plan(self)
+    }
 }
@@ -1,6 +1,12 @@
+use crate::planner::rng;
 use crate::planner::AbstractRRT;
 use crate::planner::Node;
+use crate::planner::PlannerStats;
+use crate::planner::TreeEvent;
+use crate::planner::TreeLog;
 use rand::prelude::*;
+use std::cell::Cell;
+use std::time::Instant;
 
 pub struct RRTStar<const D: usize> {
     pub start: [f32; D],
@@ -12,9 +18,18 @@ pub struct RRTStar<const D: usize> {
     pub goal_sample_rate: f32,
     pub step_size: f32,
     pub max_iter: usize,
+    /// Planning stops after this many seconds even if `max_iter` is not reached.
+    pub max_time: Option<f32>,
+    /// Axes that are angles in radians within (-pi, pi], whose differences are wrapped.
+    pub angular_axes: [bool; D],
+    /// When set, the edges of the tree are also approved at positions spaced at most this far
+    /// apart, not only at the nodes.
+    pub edge_resolution: Option<f32>,
     goal_node_index: usize,
     is_logginge_enabled: bool,
-    pub log: Vec<Vec<Node<D>>>,
+    num_collision_checks: Cell<usize>,
+    pub log: TreeLog<D>,
+    pub stats: PlannerStats,
 }
 
 impl<const D: usize> RRTStar<D> {
@@ -38,16 +53,35 @@ impl<const D: usize> RRTStar<D> {
             goal_sample_rate,
             step_size,
             max_iter,
+            max_time: None,
+            angular_axes: [false; D],
+            edge_resolution: None,
             goal_node_index: 0,
             is_logginge_enabled: false,
-            log: Vec::new(),
+            num_collision_checks: Cell::new(0),
+            log: TreeLog::new(),
+            stats: PlannerStats::default(),
         }
     }
 }
 
 impl<const D: usize> RRTStar<D> {
+    /// Records the changes to the tree in `log` from now on, starting with the current nodes.
     pub fn enable_logging(&mut self) {
         self.is_logginge_enabled = true;
+        for node in self.nodes.clone() {
+            self.log_event(TreeEvent::NodeAdded {
+                position: node.position,
+                parent: node.parent,
+                cost: node.cost,
+            });
+        }
+    }
+
+    fn log_event(&mut self, event: TreeEvent<D>) {
+        if self.is_logginge_enabled {
+            self.log.push(event);
+        }
     }
 
     pub fn add_node(&mut self, mut new_node: Node<D>, parent_node_index: usize) {
@@ -60,32 +94,55 @@ impl<const D: usize> RRTStar<D> {
         // Add new_node to nodes
         let parent_node = &self.nodes[parent_node_index];
         new_node.parent = Some(parent_node_index);
-        new_node.cost = parent_node.cost + parent_node.calc_distance(&new_node);
+        new_node.cost =
+            parent_node.cost + self.calc_distance(&parent_node.position, &new_node.position);
+        self.log_event(TreeEvent::NodeAdded {
+            position: new_node.position,
+            parent: new_node.parent,
+            cost: new_node.cost,
+        });
         self.nodes.push(new_node);
     }
 
     pub fn get_parent_node_index_minimize_cost(&self, new_node: &Node<D>) -> usize {
+        let candidate_indices = self.get_parent_candidate_indices(new_node);
+        self.choose_parent_node_index(new_node, &candidate_indices)
+    }
+
+    /// Returns the indices of the nodes within the step size of `new_node`, in increasing order.
+    fn get_parent_candidate_indices(&self, new_node: &Node<D>) -> Vec<usize> {
         let tol = 1E-5;
+        (0..self.nodes.len())
+            .filter(|&i| {
+                self.calc_distance(&self.nodes[i].position, &new_node.position)
+                    <= self.step_size + tol
+            })
+            .collect()
+    }
+
+    /// Returns the candidate through which `new_node` is reached at the lowest cost over an
+    /// approved edge, or the root if there is none.
+    fn choose_parent_node_index(&self, new_node: &Node<D>, candidate_indices: &[usize]) -> usize {
         let mut parent_node_index: usize = 0;
         let mut minimum_cost = f32::MAX;
 
-        for i in 0..self.nodes.len() {
+        for &i in candidate_indices.iter() {
             let node = &self.nodes[i];
-            let distance = node.calc_distance(&new_node);
-            let new_cost = node.cost + distance;
-            if distance <= self.step_size + tol && new_cost < minimum_cost {
+            let new_cost = node.cost + self.calc_distance(&node.position, &new_node.position);
+            if new_cost < minimum_cost && self.is_edge_approved(&node.position, &new_node.position)
+            {
                 minimum_cost = new_cost;
                 parent_node_index = i;
             }
         }
 
-        return parent_node_index;
+        parent_node_index
     }
 
     pub fn get_near_node_indices(&self, node: &Node<D>) -> Vec<usize> {
         let mut near_node_indices: Vec<usize> = Vec::new();
         for i in 0..self.nodes.len() {
-            if self.nodes[i].calc_distance(&node) <= self.step_size {
+            if self.calc_distance(&self.nodes[i].position, &node.position) <= self.step_size {
                 near_node_indices.push(i);
             }
         }
@@ -101,9 +158,15 @@ impl<const D: usize> RRTStar<D> {
 
     pub fn rewire_near_nodes(&mut self, near_node_indices: Vec<usize>, new_node_index: usize) {
         for near_node_index in near_node_indices {
-            let new_cost = self.nodes[new_node_index].cost
-                + self.nodes[new_node_index].calc_distance(&self.nodes[near_node_index]);
-            if new_cost < self.nodes[near_node_index].cost {
+            let (new_position, near_position) = (
+                self.nodes[new_node_index].position,
+                self.nodes[near_node_index].position,
+            );
+            let new_cost =
+                self.nodes[new_node_index].cost + self.calc_distance(&new_position, &near_position);
+            if new_cost < self.nodes[near_node_index].cost
+                && self.is_edge_approved(&new_position, &near_position)
+            {
                 // Delete near_node from children of the current parent node of the near node
                 let near_node_parent = self.nodes[near_node_index].parent.unwrap();
                 self.nodes[near_node_parent]
@@ -118,37 +181,67 @@ impl<const D: usize> RRTStar<D> {
 
                 let diff_cost = new_cost - self.nodes[near_node_index].cost;
                 self.update_costs(near_node_index, diff_cost);
+                self.stats.num_rewires += 1;
+                self.log_event(TreeEvent::ParentChanged {
+                    node: near_node_index,
+                    parent: new_node_index,
+                });
+                self.log_event(TreeEvent::CostUpdated {
+                    node: near_node_index,
+                    cost: new_cost,
+                });
             }
         }
     }
 
     pub fn plan(&mut self) -> Vec<[f32; D]> {
+        let start_time = Instant::now();
+        self.stats = PlannerStats::default();
+        self.num_collision_checks.set(0);
         let mut is_goaled = false;
 
         for _ in 0..self.max_iter {
+            if let Some(max_time) = self.max_time {
+                if max_time <= start_time.elapsed().as_secs_f32() {
+                    break;
+                }
+            }
+            self.stats.num_iterations += 1;
+
             // Sample a node
             let mut new_node;
-            if thread_rng().gen::<f32>() < self.goal_sample_rate {
+            if rng().gen::<f32>() < self.goal_sample_rate {
                 new_node = Node::new(self.goal);
             } else {
                 new_node = self.sample();
             }
 
             // Get the nearest node
+            let neighbor_search_start_time = Instant::now();
             let nearest_node_index = self.get_nearest_node_index(&new_node);
+            self.stats.nearest_neighbor_time += neighbor_search_start_time.elapsed().as_secs_f32();
             let nearest_node = &self.nodes[nearest_node_index];
 
-            let distance_from_nearest_node = nearest_node.calc_distance(&new_node);
+            let distance_from_nearest_node =
+                self.calc_distance(&nearest_node.position, &new_node.position);
             if self.step_size < distance_from_nearest_node {
                 new_node = self.get_extended_node(nearest_node, &new_node);
             }
 
-            if !(self.is_approved)(&new_node.position) {
+            if !self.is_position_approved(&new_node.position) {
+                self.stats.num_rejected_samples += 1;
+                continue;
+            }
+            if !self.is_edge_approved(&nearest_node.position, &new_node.position) {
+                self.stats.num_rejected_edges += 1;
                 continue;
             }
 
             // Add the new node to the tree
-            let parent_node_index = self.get_parent_node_index_minimize_cost(&new_node);
+            let neighbor_search_start_time = Instant::now();
+            let candidate_indices = self.get_parent_candidate_indices(&new_node);
+            self.stats.nearest_neighbor_time += neighbor_search_start_time.elapsed().as_secs_f32();
+            let parent_node_index = self.choose_parent_node_index(&new_node, &candidate_indices);
             self.add_node(new_node, parent_node_index);
 
             // Add the new node to the tree
@@ -163,14 +256,27 @@ impl<const D: usize> RRTStar<D> {
             // Rewire near nodes
             let new_node_index = self.nodes.len() - 1;
             let new_node = &self.nodes[new_node_index];
+            let neighbor_search_start_time = Instant::now();
             let near_node_indices = self.get_near_node_indices(new_node);
+            self.stats.nearest_neighbor_time += neighbor_search_start_time.elapsed().as_secs_f32();
             self.rewire_near_nodes(near_node_indices, new_node_index);
 
-            if self.is_logginge_enabled {
-                self.log.push(self.nodes.clone());
+            if is_goaled {
+                let cost = self.nodes[self.goal_node_index].cost;
+                if self.stats.get_best_cost() != Some(cost) {
+                    self.log_event(TreeEvent::SolutionImproved {
+                        goal_node: self.goal_node_index,
+                        cost,
+                    });
+                }
+                self.stats
+                    .record_cost(start_time.elapsed().as_secs_f32(), cost);
             }
+
+            self.log_event(TreeEvent::Iteration);
         }
 
+        self.stats.num_collision_checks = self.num_collision_checks.get();
         return self.extract_path();
     }
 }
@@ -194,4 +300,30 @@ impl<const D: usize> AbstractRRT<D> for RRTStar<D> {
     fn get_nodes(&self) -> &Vec<Node<D>> {
         &self.nodes
     }
+    fn get_stats(&self) -> &PlannerStats {
+        &self.stats
+    }
+    fn get_angular_axes(&self) -> &[bool; D] {
+        &self.angular_axes
+    }
+    fn get_edge_resolution(&self) -> Option<f32> {
+        self.edge_resolution
+    }
+    fn set_max_time(&mut self, max_time: Option<f32>) {
+        self.max_time = max_time;
+    }
+    fn get_log(&self) -> &TreeLog<D> {
+        &self.log
+    }
+    fn enable_logging(&mut self) {
+        RRTStar::enable_logging(self)
+    }
+    fn is_position_approved(&self, position: &[f32; D]) -> bool {
+        self.num_collision_checks
+            .set(self.num_collision_checks.get() + 1);
+        (self.is_approved)(position)
+    }
+    fn plan(&mut self) -> Vec<[f32; D]> {
+        RRTStar::plan(self)
+    }
 }
@@ -0,0 +1,37 @@
+/// Statistics collected by a planner during `plan`. Times are in seconds since the start of
+/// `plan`.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlannerStats {
+    pub num_iterations: usize,
+    /// Number of calls to `is_approved`, including those checking the nodes and edges of the tree.
+    pub num_collision_checks: usize,
+    /// Number of samples for which `is_approved` returned false.
+    pub num_rejected_samples: usize,
+    /// Number of approved samples whose edge from the nearest node was not approved.
+    pub num_rejected_edges: usize,
+    /// Total time spent scanning the tree for nearest and near nodes, excluding collision checks.
+    pub nearest_neighbor_time: f32,
+    /// Number of nodes whose parent was changed by rewiring.
+    pub num_rewires: usize,
+    pub time_to_first_solution: Option<f32>,
+    /// Time and cost of the best solution each time it improves.
+    pub cost_history: Vec<(f32, f32)>,
+}
+
+impl PlannerStats {
+    /// Records the cost of the best solution if it differs from the last recorded one.
+    pub fn record_cost(&mut self, time: f32, cost: f32) {
+        if self.time_to_first_solution.is_none() {
+            self.time_to_first_solution = Some(time);
+        }
+        match self.cost_history.last() {
+            Some((_, last_cost)) if *last_cost == cost => {}
+            _ => self.cost_history.push((time, cost)),
+        }
+    }
+
+    pub fn get_best_cost(&self) -> Option<f32> {
+        self.cost_history.last().map(|(_, cost)| *cost)
+    }
+}
@@ -0,0 +1,237 @@
+use crate::planner::Node;
+
+/// Change to the tree of a planner, recorded instead of copying the whole tree.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TreeEvent<const D: usize> {
+    /// Marks the end of an iteration that changed the tree.
+    Iteration,
+    NodeAdded {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::array"))]
+        position: [f32; D],
+        parent: Option<usize>,
+        cost: f32,
+    },
+    ParentChanged {
+        node: usize,
+        parent: usize,
+    },
+    /// The costs of the descendants of `node` change by the same amount.
+    CostUpdated {
+        node: usize,
+        cost: f32,
+    },
+    SolutionImproved {
+        goal_node: usize,
+        cost: f32,
+    },
+}
+
+/// Tree reconstructed from a prefix of a `TreeLog`.
+pub struct TreeSnapshot<const D: usize> {
+    pub nodes: Vec<Node<D>>,
+    /// Goal node of the best solution found so far.
+    pub goal_node_index: Option<usize>,
+}
+
+impl<const D: usize> TreeSnapshot<D> {
+    pub fn new() -> Self {
+        TreeSnapshot {
+            nodes: Vec::new(),
+            goal_node_index: None,
+        }
+    }
+
+    pub fn apply(&mut self, event: &TreeEvent<D>) {
+        match event {
+            TreeEvent::Iteration => {}
+            TreeEvent::NodeAdded {
+                position,
+                parent,
+                cost,
+            } => {
+                let mut node = Node::new(*position);
+                node.parent = *parent;
+                node.cost = *cost;
+                let node_index = self.nodes.len();
+                if let Some(parent) = parent {
+                    self.nodes[*parent].children.insert(node_index);
+                }
+                self.nodes.push(node);
+            }
+            TreeEvent::ParentChanged { node, parent } => {
+                if let Some(old_parent) = self.nodes[*node].parent {
+                    self.nodes[old_parent].children.remove(node);
+                }
+                self.nodes[*node].parent = Some(*parent);
+                self.nodes[*parent].children.insert(*node);
+            }
+            TreeEvent::CostUpdated { node, cost } => {
+                let diff_cost = cost - self.nodes[*node].cost;
+                let mut stack = vec![*node];
+                while let Some(index) = stack.pop() {
+                    self.nodes[index].cost += diff_cost;
+                    stack.extend(self.nodes[index].children.iter());
+                }
+            }
+            TreeEvent::SolutionImproved { goal_node, .. } => {
+                self.goal_node_index = Some(*goal_node);
+            }
+        }
+    }
+
+    /// Returns the path from the root to the goal node of the best solution, or an empty path if
+    /// no solution has been found.
+    pub fn extract_path(&self) -> Vec<[f32; D]> {
+        let mut reverse_path: Vec<[f32; D]> = Vec::new();
+        let mut node_index = self.goal_node_index;
+        while let Some(index) = node_index {
+            reverse_path.push(self.nodes[index].position);
+            node_index = self.nodes[index].parent;
+        }
+        reverse_path.into_iter().rev().collect()
+    }
+}
+
+/// Sequence of tree events from which the tree after any logged iteration can be rebuilt.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeLog<const D: usize> {
+    pub events: Vec<TreeEvent<D>>,
+}
+
+impl<const D: usize> TreeLog<D> {
+    pub fn new() -> Self {
+        TreeLog { events: Vec::new() }
+    }
+
+    pub fn push(&mut self, event: TreeEvent<D>) {
+        self.events.push(event);
+    }
+
+    pub fn get_num_snapshots(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|event| matches!(event, TreeEvent::Iteration))
+            .count()
+    }
+
+    /// Returns the tree at the end of the `index`-th logged iteration.
+    pub fn snapshot(&self, index: usize) -> TreeSnapshot<D> {
+        let mut snapshot = TreeSnapshot::new();
+        let mut num_iterations = 0;
+        for event in self.events.iter() {
+            snapshot.apply(event);
+            if let TreeEvent::Iteration = event {
+                if num_iterations == index {
+                    break;
+                }
+                num_iterations += 1;
+            }
+        }
+        snapshot
+    }
+
+    /// Replays the events once and calls `visit` with the index of each logged iteration and the
+    /// tree at its end, without copying the tree. Stops at the first error of `visit`.
+    pub fn replay<E, F>(&self, mut visit: F) -> Result<(), E>
+    where
+        F: FnMut(usize, &TreeSnapshot<D>) -> Result<(), E>,
+    {
+        let mut snapshot = TreeSnapshot::new();
+        let mut num_iterations = 0;
+        for event in self.events.iter() {
+            snapshot.apply(event);
+            if let TreeEvent::Iteration = event {
+                visit(num_iterations, &snapshot)?;
+                num_iterations += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over the trees at the end of each logged iteration, replaying the
+    /// events only once. Each tree is a copy, so `replay` is cheaper when only some of them are
+    /// needed.
+    pub fn snapshots(&self) -> Snapshots<'_, D> {
+        Snapshots {
+            events: self.events.iter(),
+            snapshot: TreeSnapshot::new(),
+        }
+    }
+}
+
+pub struct Snapshots<'a, const D: usize> {
+    events: std::slice::Iter<'a, TreeEvent<D>>,
+    snapshot: TreeSnapshot<D>,
+}
+
+impl<'a, const D: usize> Iterator for Snapshots<'a, D> {
+    type Item = TreeSnapshot<D>;
+
+    fn next(&mut self) -> Option<TreeSnapshot<D>> {
+        for event in self.events.by_ref() {
+            self.snapshot.apply(event);
+            if let TreeEvent::Iteration = event {
+                return Some(self.snapshot.clone());
+            }
+        }
+        None
+    }
+}
+
+impl<const D: usize> Default for TreeSnapshot<D> {
+    fn default() -> Self {
+        TreeSnapshot::new()
+    }
+}
+
+impl<const D: usize> Default for TreeLog<D> {
+    fn default() -> Self {
+        TreeLog::new()
+    }
+}
+
+impl<const D: usize> Clone for TreeEvent<D> {
+    fn clone(&self) -> TreeEvent<D> {
+        match self {
+            TreeEvent::Iteration => TreeEvent::Iteration,
+            TreeEvent::NodeAdded {
+                position,
+                parent,
+                cost,
+            } => TreeEvent::NodeAdded {
+                position: *position,
+                parent: *parent,
+                cost: *cost,
+            },
+            TreeEvent::ParentChanged { node, parent } => TreeEvent::ParentChanged {
+                node: *node,
+                parent: *parent,
+            },
+            TreeEvent::CostUpdated { node, cost } => TreeEvent::CostUpdated {
+                node: *node,
+                cost: *cost,
+            },
+            TreeEvent::SolutionImproved { goal_node, cost } => TreeEvent::SolutionImproved {
+                goal_node: *goal_node,
+                cost: *cost,
+            },
+        }
+    }
+}
+
+impl<const D: usize> Clone for TreeSnapshot<D> {
+    fn clone(&self) -> TreeSnapshot<D> {
+        TreeSnapshot {
+            nodes: self.nodes.clone(),
+            goal_node_index: self.goal_node_index,
+        }
+    }
+}
+
+impl<const D: usize> Clone for TreeLog<D> {
+    fn clone(&self) -> TreeLog<D> {
+        TreeLog {
+            events: self.events.clone(),
+        }
+    }
+}
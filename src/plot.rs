@@ -1,4 +1,27 @@
+mod animation;
+mod figure;
+mod mesh;
 mod plot2d;
-pub use crate::plot::plot2d::animate_path;
+mod plot3d;
+mod plot_builder;
+pub use crate::plot::animation::animate_path;
+pub use crate::plot::animation::animate_path_on;
+pub use crate::plot::animation::animate_path_to;
+pub use crate::plot::animation::AnimationMode;
+pub use crate::plot::animation::AnimationOptions;
+pub use crate::plot::figure::Figure;
+pub use crate::plot::mesh::create_env_mesh;
+pub use crate::plot::mesh::create_obstacle_mesh;
+pub use crate::plot::mesh::create_path_mesh;
+pub use crate::plot::mesh::create_tree_mesh;
+pub use crate::plot::mesh::save_obj;
+pub use crate::plot::mesh::to_obj_string;
+pub use crate::plot::mesh::Mesh;
 pub use crate::plot::plot2d::plot_env;
 pub use crate::plot::plot2d::plot_path;
+pub use crate::plot::plot2d::EnvFigure;
+pub use crate::plot::plot2d::PathFigure;
+pub use crate::plot::plot2d::DEFAULT_SIZE;
+pub use crate::plot::plot3d::PlotBuilder3d;
+pub use crate::plot::plot_builder::PlotBuilder;
+pub use crate::plot::plot_builder::PATH_COLORS;
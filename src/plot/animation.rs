@@ -0,0 +1,207 @@
+use crate::env::Env;
+use crate::planner::{TreeLog, TreeSnapshot};
+use crate::plot::plot2d::{
+    build_chart, calc_circle_points, draw_env, draw_path, draw_tree, Chart, DEFAULT_SIZE,
+};
+use crate::plot::plot_builder::{calc_informed_ellipse_points, ELLIPSE_COLOR};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::error::Error;
+use std::path::Path;
+
+/// What each frame of an animation shows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnimationMode {
+    /// The tree and the best path after each logged iteration.
+    TreeGrowth,
+    /// The best path after each iteration that changed its cost.
+    SolutionImprovement,
+    /// An agent of `radius` moving along the final path at `speed` units per second.
+    PathExecution { radius: f32, speed: f32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct AnimationOptions {
+    pub mode: AnimationMode,
+    /// Size of the frames in pixels.
+    pub size: (u32, u32),
+    /// Frames per second of the saved animation.
+    pub fps: f32,
+    /// Only every `frame_step`-th frame is drawn. The last frame is always drawn.
+    pub frame_step: usize,
+    /// Draws the informed ellipse of the best solution in the `TreeGrowth` and
+    /// `SolutionImprovement` modes.
+    pub is_ellipse_shown: bool,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        AnimationOptions {
+            mode: AnimationMode::TreeGrowth,
+            size: DEFAULT_SIZE,
+            fps: 20.0,
+            frame_step: 1,
+            is_ellipse_shown: false,
+        }
+    }
+}
+
+fn draw_informed_ellipse<DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    snapshot: &TreeSnapshot<2>,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    if let Some(goal_node_index) = snapshot.goal_node_index {
+        let goal_node = &snapshot.nodes[goal_node_index];
+        let mut points = calc_informed_ellipse_points(
+            &snapshot.nodes[0].position,
+            &goal_node.position,
+            goal_node.cost,
+        );
+        if !points.is_empty() {
+            points.push(points[0]);
+            chart.draw_series([PathElement::new(points, ELLIPSE_COLOR.stroke_width(2))])?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether the `index`-th of `num_frames` frames is drawn: every `frame_step`-th frame and
+/// the last frame are.
+fn is_frame_drawn(index: usize, num_frames: usize, frame_step: usize) -> bool {
+    index.is_multiple_of(frame_step.max(1)) || index + 1 == num_frames
+}
+
+/// Draws the frames of the animation of the planning recorded in `log` on `root`, e.g. a GIF
+/// backend.
+pub fn animate_path_on<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    env: &Env<2>,
+    log: &TreeLog<2>,
+    options: &AnimationOptions,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let mut chart = build_chart(root, env)?;
+
+    match options.mode {
+        AnimationMode::TreeGrowth => {
+            let num_snapshots = log.get_num_snapshots();
+            log.replay(|k, snapshot| {
+                if !is_frame_drawn(k, num_snapshots, options.frame_step) {
+                    return Ok(());
+                }
+                root.fill(&WHITE)?;
+                chart.configure_mesh().draw()?;
+
+                draw_env(&mut chart, env)?;
+                draw_tree(&mut chart, &snapshot.nodes)?;
+                if options.is_ellipse_shown {
+                    draw_informed_ellipse(&mut chart, snapshot)?;
+                }
+                draw_path(&mut chart, &snapshot.extract_path())?;
+
+                root.present()?;
+                Ok(())
+            })
+        }
+        AnimationMode::SolutionImprovement => {
+            // Find the iterations that changed the cost of the best solution before drawing any
+            let mut improved_indices: Vec<usize> = Vec::new();
+            let mut best_cost: Option<f32> = None;
+            log.replay(|k, snapshot| {
+                let cost = snapshot
+                    .goal_node_index
+                    .map(|index| snapshot.nodes[index].cost);
+                if cost.is_some() && cost != best_cost {
+                    improved_indices.push(k);
+                    best_cost = cost;
+                }
+                Ok::<(), Box<dyn Error>>(())
+            })?;
+            let num_frames = improved_indices.len();
+            let drawn_indices: Vec<usize> = improved_indices
+                .into_iter()
+                .enumerate()
+                .filter(|(j, _)| is_frame_drawn(*j, num_frames, options.frame_step))
+                .map(|(_, k)| k)
+                .collect();
+
+            log.replay(|k, snapshot| {
+                if drawn_indices.binary_search(&k).is_err() {
+                    return Ok(());
+                }
+                root.fill(&WHITE)?;
+                chart.configure_mesh().draw()?;
+
+                draw_env(&mut chart, env)?;
+                if options.is_ellipse_shown {
+                    draw_informed_ellipse(&mut chart, snapshot)?;
+                }
+                draw_path(&mut chart, &snapshot.extract_path())?;
+
+                root.present()?;
+                Ok(())
+            })
+        }
+        AnimationMode::PathExecution { radius, speed } => {
+            let num_snapshots = log.get_num_snapshots();
+            let final_path = if num_snapshots > 0 {
+                log.snapshot(num_snapshots - 1).extract_path()
+            } else {
+                Vec::new()
+            };
+            if final_path.is_empty() {
+                return Err("the log contains no solution".into());
+            }
+
+            let path = crate::path::Path::new(final_path.clone());
+            let length = path.get_length();
+            let distance_per_frame = speed / options.fps;
+            if distance_per_frame <= 0.0 || !distance_per_frame.is_finite() {
+                return Err("the speed and the frame rate must be positive".into());
+            }
+            let num_frames = (length / distance_per_frame).ceil() as usize + 1;
+            for k in 0..num_frames {
+                if !is_frame_drawn(k, num_frames, options.frame_step) {
+                    continue;
+                }
+                let position = path.interpolate((k as f32 * distance_per_frame).min(length));
+                root.fill(&WHITE)?;
+                chart.configure_mesh().draw()?;
+
+                draw_env(&mut chart, env)?;
+                draw_path(&mut chart, &final_path)?;
+                let agent = Polygon::new(calc_circle_points(&position, radius), BLUE.filled());
+                chart.draw_series([agent])?;
+
+                root.present()?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Saves the animation of the planning recorded in `log` as a GIF. Fails if the frame rate is not
+/// positive and finite.
+pub fn animate_path_to<P: AsRef<Path>>(
+    env: &Env<2>,
+    log: &TreeLog<2>,
+    path: P,
+    options: &AnimationOptions,
+) -> Result<(), Box<dyn Error>> {
+    if !(options.fps > 0.0 && options.fps.is_finite()) {
+        return Err("the frame rate must be positive and finite".into());
+    }
+    let frame_delay = (1000.0 / options.fps).round() as u32;
+    let root = BitMapBackend::gif(path, options.size, frame_delay)?.into_drawing_area();
+    animate_path_on(&root, env, log, options)
+}
+
+/// Saves the growth of the tree to `log.gif`.
+pub fn animate_path(env: &Env<2>, log: &TreeLog<2>) -> Result<(), Box<dyn Error>> {
+    animate_path_to(env, log, "log.gif", &AnimationOptions::default())
+}
@@ -0,0 +1,48 @@
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::error::Error;
+use std::path::Path;
+
+/// Plot that can be drawn on any plotters backend, and saved to a file or rendered in memory.
+pub trait Figure {
+    /// Draws the whole figure, including the background, on `root`.
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> Result<(), Box<dyn Error>>
+    where
+        DB::ErrorType: 'static;
+
+    /// Saves the figure as an SVG file if `path` ends with `.svg` and as a bitmap image otherwise.
+    fn save<P: AsRef<Path>>(&self, path: P, size: (u32, u32)) -> Result<(), Box<dyn Error>> {
+        let path = path.as_ref();
+        if path.extension().is_some_and(|extension| extension == "svg") {
+            let root = SVGBackend::new(path, size).into_drawing_area();
+            self.draw(&root)?;
+            root.present()?;
+        } else {
+            let root = BitMapBackend::new(path, size).into_drawing_area();
+            self.draw(&root)?;
+            root.present()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the pixels of the figure as RGB bytes, row by row from the upper left corner.
+    fn to_rgb(&self, size: (u32, u32)) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut buffer = vec![0; size.0 as usize * size.1 as usize * 3];
+        {
+            let root = BitMapBackend::with_buffer(&mut buffer, size).into_drawing_area();
+            self.draw(&root)?;
+            root.present()?;
+        }
+        Ok(buffer)
+    }
+
+    fn to_svg_string(&self, size: (u32, u32)) -> Result<String, Box<dyn Error>> {
+        let mut svg = String::new();
+        {
+            let root = SVGBackend::with_string(&mut svg, size).into_drawing_area();
+            self.draw(&root)?;
+            root.present()?;
+        }
+        Ok(svg)
+    }
+}
@@ -0,0 +1,387 @@
+use crate::env::Env;
+use crate::env::Obstacle;
+use crate::planner::Node;
+use std::error::Error;
+use std::f32::consts::PI;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const NUM_LATITUDES: usize = 16;
+const NUM_LONGITUDES: usize = 24;
+const NUM_VOXELS: usize = 32;
+
+/// Corners of the faces of the unit box whose corners are indexed by `x + 2 * y + 4 * z`, ordered
+/// counterclockwise seen from outside, for the -x, +x, -y, +y, -z and +z directions.
+const BOX_FACES: [[usize; 4]; 6] = [
+    [0, 4, 6, 2],
+    [1, 3, 7, 5],
+    [0, 1, 5, 4],
+    [2, 6, 7, 3],
+    [0, 2, 3, 1],
+    [4, 5, 7, 6],
+];
+
+/// Triangles and polylines that are exported as one object of an OBJ file.
+pub struct Mesh {
+    pub name: String,
+    pub vertices: Vec<[f32; 3]>,
+    /// Vertex indices of triangles, ordered counterclockwise seen from outside.
+    pub faces: Vec<[usize; 3]>,
+    /// Vertex indices of polylines.
+    pub lines: Vec<Vec<usize>>,
+}
+
+impl Mesh {
+    pub fn new(name: &str) -> Self {
+        Mesh {
+            name: name.to_string(),
+            vertices: Vec::new(),
+            faces: Vec::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn append(&mut self, other: &Mesh) {
+        let offset = self.vertices.len();
+        self.vertices.extend(other.vertices.iter());
+        self.faces.extend(
+            other
+                .faces
+                .iter()
+                .map(|face| [face[0] + offset, face[1] + offset, face[2] + offset]),
+        );
+        self.lines.extend(
+            other
+                .lines
+                .iter()
+                .map(|line| line.iter().map(|index| index + offset).collect()),
+        );
+    }
+
+    /// Returns the unit normal of the `index`-th face.
+    pub fn calc_face_normal(&self, index: usize) -> [f32; 3] {
+        let [a, b, c] = self.faces[index].map(|vertex_index| self.vertices[vertex_index]);
+        let normal = cross(&sub(&b, &a), &sub(&c, &a));
+        let norm = dot(&normal, &normal).sqrt();
+        if norm == 0.0 {
+            return [0.0, 0.0, 0.0];
+        }
+        normal.map(|x| x / norm)
+    }
+
+    fn add_quad(&mut self, corners: [[f32; 3]; 4]) {
+        let offset = self.vertices.len();
+        self.vertices.extend(corners.iter());
+        self.faces.push([offset, offset + 1, offset + 2]);
+        self.faces.push([offset, offset + 2, offset + 3]);
+    }
+
+    /// Adds a box with the given center, half sizes and axes.
+    fn add_box(&mut self, center: &[f32; 3], half_size: &[f32; 3], axes: &[[f32; 3]; 3]) {
+        let corners: Vec<[f32; 3]> = (0..8)
+            .map(|index| {
+                let mut corner = *center;
+                for i in 0..3 {
+                    let sign = if (index >> i) & 1 == 1 { 1.0 } else { -1.0 };
+                    for j in 0..3 {
+                        corner[j] += sign * half_size[i] * axes[i][j];
+                    }
+                }
+                corner
+            })
+            .collect();
+
+        let offset = self.vertices.len();
+        self.vertices.extend(corners);
+        for [a, b, c, d] in BOX_FACES {
+            self.faces.push([offset + a, offset + b, offset + c]);
+            self.faces.push([offset + a, offset + c, offset + d]);
+        }
+    }
+
+    /// Adds the ellipsoid with the given radii along `axes`, with its lower half around
+    /// `lower_center` and its upper half around `upper_center` joined by a tube in between.
+    fn add_swept_ellipsoid(
+        &mut self,
+        lower_center: &[f32; 3],
+        upper_center: &[f32; 3],
+        axes: &[[f32; 3]; 3],
+        radiuses: &[f32; 3],
+    ) {
+        let to_world = |center: &[f32; 3], latitude: f32, longitude: f32| {
+            let local = [
+                radiuses[0] * latitude.cos() * longitude.cos(),
+                radiuses[1] * latitude.cos() * longitude.sin(),
+                radiuses[2] * latitude.sin(),
+            ];
+            let mut position = *center;
+            for i in 0..3 {
+                for j in 0..3 {
+                    position[j] += local[i] * axes[i][j];
+                }
+            }
+            position
+        };
+
+        // Rings of vertices from the lower pole to the upper pole, excluding the poles
+        let half = NUM_LATITUDES / 2;
+        let mut rings: Vec<(&[f32; 3], f32)> = Vec::new();
+        for k in 1..=half {
+            rings.push((
+                lower_center,
+                -PI / 2.0 + PI * k as f32 / NUM_LATITUDES as f32,
+            ));
+        }
+        let start = if lower_center == upper_center { 1 } else { 0 };
+        for k in start..half {
+            rings.push((upper_center, PI * k as f32 / NUM_LATITUDES as f32));
+        }
+
+        let lower_pole_index = self.vertices.len();
+        self.vertices.push(to_world(lower_center, -PI / 2.0, 0.0));
+        for (center, latitude) in rings.iter() {
+            for l in 0..NUM_LONGITUDES {
+                let longitude = 2.0 * PI * l as f32 / NUM_LONGITUDES as f32;
+                self.vertices.push(to_world(center, *latitude, longitude));
+            }
+        }
+        let upper_pole_index = self.vertices.len();
+        self.vertices.push(to_world(upper_center, PI / 2.0, 0.0));
+
+        let ring_index =
+            |k: usize, l: usize| lower_pole_index + 1 + k * NUM_LONGITUDES + l % NUM_LONGITUDES;
+        for l in 0..NUM_LONGITUDES {
+            self.faces
+                .push([lower_pole_index, ring_index(0, l + 1), ring_index(0, l)]);
+            for k in 0..rings.len() - 1 {
+                self.faces.push([
+                    ring_index(k, l),
+                    ring_index(k, l + 1),
+                    ring_index(k + 1, l + 1),
+                ]);
+                self.faces.push([
+                    ring_index(k, l),
+                    ring_index(k + 1, l + 1),
+                    ring_index(k + 1, l),
+                ]);
+            }
+            let k = rings.len() - 1;
+            self.faces
+                .push([ring_index(k, l), ring_index(k, l + 1), upper_pole_index]);
+        }
+    }
+
+    /// Adds the boundary faces of the voxels over the environment whose centers are inside the
+    /// obstacle.
+    fn add_voxels(&mut self, env: &Env<3>, obstacle: &Obstacle<3>) {
+        let (aabb_low, aabb_high) = obstacle.calc_aabb();
+        let mut low = [0.0; 3];
+        let mut high = [0.0; 3];
+        for i in 0..3 {
+            low[i] = aabb_low[i].max(env.low[i]);
+            high[i] = aabb_high[i].min(env.high[i]);
+            if high[i] <= low[i] {
+                return;
+            }
+        }
+
+        let voxel_size = (0..3).map(|i| high[i] - low[i]).fold(0.0, f32::max) / NUM_VOXELS as f32;
+        let num_voxels = [0, 1, 2].map(|i| ((high[i] - low[i]) / voxel_size).ceil() as usize);
+        let mut is_inside = vec![false; num_voxels[0] * num_voxels[1] * num_voxels[2]];
+        let voxel_index =
+            |ix: usize, iy: usize, iz: usize| (iz * num_voxels[1] + iy) * num_voxels[0] + ix;
+        for iz in 0..num_voxels[2] {
+            for iy in 0..num_voxels[1] {
+                for ix in 0..num_voxels[0] {
+                    let center = [ix, iy, iz].map(|k| k as f32 + 0.5);
+                    let position = [0, 1, 2].map(|i| low[i] + center[i] * voxel_size);
+                    is_inside[voxel_index(ix, iy, iz)] = obstacle.is_inside(&position);
+                }
+            }
+        }
+
+        for iz in 0..num_voxels[2] {
+            for iy in 0..num_voxels[1] {
+                for ix in 0..num_voxels[0] {
+                    if !is_inside[voxel_index(ix, iy, iz)] {
+                        continue;
+                    }
+                    let cell = [ix, iy, iz];
+                    for (direction, face) in BOX_FACES.iter().enumerate() {
+                        // Only faces between an inside and an outside voxel are visible
+                        let axis = direction / 2;
+                        let mut neighbor = cell.map(|k| k as i64);
+                        neighbor[axis] += if direction % 2 == 1 { 1 } else { -1 };
+                        let is_neighbor_inside = (0..3)
+                            .all(|i| 0 <= neighbor[i] && neighbor[i] < num_voxels[i] as i64)
+                            && is_inside[voxel_index(
+                                neighbor[0] as usize,
+                                neighbor[1] as usize,
+                                neighbor[2] as usize,
+                            )];
+                        if is_neighbor_inside {
+                            continue;
+                        }
+
+                        let corners = face.map(|corner_index| {
+                            [0, 1, 2].map(|i| {
+                                let k = cell[i] + ((corner_index >> i) & 1);
+                                low[i] + k as f32 * voxel_size
+                            })
+                        });
+                        self.add_quad(corners);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the surface of the obstacle. Obstacles without a closed form surface are approximated
+/// with voxels inside the bounds of the environment.
+pub fn create_obstacle_mesh(env: &Env<3>, obstacle: &Obstacle<3>) -> Mesh {
+    let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    let mut mesh = Mesh::new("obstacle");
+    match obstacle {
+        Obstacle::RectObstacle { center, size } => {
+            mesh.add_box(center, &size.map(|x| x / 2.0), &identity);
+        }
+        Obstacle::OrientedRectObstacle {
+            center,
+            size,
+            rotation,
+        } => {
+            mesh.add_box(center, &size.map(|x| x / 2.0), &get_axes(rotation));
+        }
+        Obstacle::CircleObstacle { center, radius } => {
+            mesh.add_swept_ellipsoid(center, center, &identity, &[*radius; 3]);
+        }
+        Obstacle::EllipsoidObstacle {
+            center,
+            radiuses,
+            rotation,
+        } => {
+            mesh.add_swept_ellipsoid(center, center, &get_axes(rotation), radiuses);
+        }
+        Obstacle::CapsuleObstacle { start, end, radius } => {
+            let axes = calc_frame(&sub(end, start));
+            mesh.add_swept_ellipsoid(start, end, &axes, &[*radius; 3]);
+        }
+        Obstacle::PolygonObstacle { .. }
+        | Obstacle::PolytopeObstacle { .. }
+        | Obstacle::UnionObstacle { .. }
+        | Obstacle::IntersectionObstacle { .. }
+        | Obstacle::DifferenceObstacle { .. }
+        | Obstacle::InflatedObstacle { .. } => {
+            mesh.add_voxels(env, obstacle);
+        }
+    }
+    mesh
+}
+
+/// Returns the surfaces of all obstacles of the environment as one mesh.
+pub fn create_env_mesh(env: &Env<3>) -> Mesh {
+    let mut mesh = Mesh::new("obstacles");
+    for obstacle in env.get_obstacles().iter() {
+        mesh.append(&create_obstacle_mesh(env, obstacle));
+    }
+    mesh
+}
+
+/// Returns the edges between the nodes and their parents.
+pub fn create_tree_mesh(nodes: &[Node<3>]) -> Mesh {
+    let mut mesh = Mesh::new("tree");
+    mesh.vertices = nodes.iter().map(|node| node.position).collect();
+    mesh.lines = nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, node)| node.parent.map(|parent_index| vec![parent_index, i]))
+        .collect();
+    mesh
+}
+
+pub fn create_path_mesh(path: &[[f32; 3]]) -> Mesh {
+    let mut mesh = Mesh::new("path");
+    mesh.vertices = path.to_vec();
+    if path.len() >= 2 {
+        mesh.lines.push((0..path.len()).collect());
+    }
+    mesh
+}
+
+/// Returns the meshes in the Wavefront OBJ format, one object per mesh.
+pub fn to_obj_string(meshes: &[Mesh]) -> String {
+    let mut s = String::new();
+    let mut offset = 1;
+    for mesh in meshes.iter() {
+        writeln!(s, "o {}", mesh.name).unwrap();
+        for vertex in mesh.vertices.iter() {
+            writeln!(s, "v {} {} {}", vertex[0], vertex[1], vertex[2]).unwrap();
+        }
+        for face in mesh.faces.iter() {
+            writeln!(
+                s,
+                "f {} {} {}",
+                face[0] + offset,
+                face[1] + offset,
+                face[2] + offset
+            )
+            .unwrap();
+        }
+        for line in mesh.lines.iter() {
+            let indices: Vec<String> = line
+                .iter()
+                .map(|index| (index + offset).to_string())
+                .collect();
+            writeln!(s, "l {}", indices.join(" ")).unwrap();
+        }
+        offset += mesh.vertices.len();
+    }
+    s
+}
+
+pub fn save_obj<P: AsRef<Path>>(path: P, meshes: &[Mesh]) -> Result<(), Box<dyn Error>> {
+    fs::write(path, to_obj_string(meshes))?;
+    Ok(())
+}
+
+/// Returns the columns of the rotation matrix, which are the axes of the rotated frame.
+fn get_axes(rotation: &[[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    [0, 1, 2].map(|i| [rotation[0][i], rotation[1][i], rotation[2][i]])
+}
+
+/// Returns a right-handed orthonormal frame whose third axis points along `direction`.
+fn calc_frame(direction: &[f32; 3]) -> [[f32; 3]; 3] {
+    let norm = dot(direction, direction).sqrt();
+    if norm == 0.0 {
+        return [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    }
+    let z = direction.map(|x| x / norm);
+    let helper = if z[0].abs() < 0.9 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    let y = cross(&z, &helper);
+    let y_norm = dot(&y, &y).sqrt();
+    let y = y.map(|x| x / y_norm);
+    let x = cross(&y, &z);
+    [x, y, z]
+}
+
+fn sub(a: &[f32; 3], b: &[f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub(crate) fn dot(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: &[f32; 3], b: &[f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
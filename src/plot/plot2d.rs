@@ -1,18 +1,35 @@
 use crate::env::Env;
 use crate::env::Obstacle;
 use crate::planner::Node;
+use crate::plot::Figure;
 use plotters::coord::types::RangedCoordf32;
+use plotters::coord::Shift;
 use plotters::prelude::*;
+use std::error::Error;
+use std::f32::consts::PI;
+use std::ops::Range;
 
 const MARGIN: i32 = 20;
 const X_LABEL_AREA_SIZE: i32 = 30;
 const Y_LABEL_AREA_SIZE: i32 = 30;
+const NUM_ARC_POINTS: usize = 64;
+const NUM_RASTER_CELLS: usize = 400;
 
-fn draw_env(
-    chart: &mut ChartContext<BitMapBackend, Cartesian2d<RangedCoordf32, RangedCoordf32>>,
+/// Image size used by `plot_env`, `plot_path` and `animate_path`.
+pub const DEFAULT_SIZE: (u32, u32) = (1000, 600);
+
+pub(crate) const TREE_COLOR: RGBColor = RGBColor(170, 170, 170);
+
+pub(crate) type Chart<'a, DB> = ChartContext<'a, DB, Cartesian2d<RangedCoordf32, RangedCoordf32>>;
+
+pub(crate) fn draw_env<DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
     env: &Env<2>,
-) {
-    for (i, obs) in env.obstacles.iter().enumerate() {
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    for (i, obs) in env.get_obstacles().iter().enumerate() {
         match obs {
             Obstacle::RectObstacle { center, size } => {
                 let left_upper_corner = (center[0] - size[0] / 2.0, center[1] - size[1] / 2.0);
@@ -21,122 +38,302 @@ fn draw_env(
                     [left_upper_corner, right_lower_corner],
                     Palette99::pick(i + 1).filled(),
                 );
-                chart.draw_series([rect]).unwrap();
+                chart.draw_series([rect])?;
             }
             Obstacle::CircleObstacle { center, radius } => {
-                let circle = Circle::new(
-                    (center[0], center[1]),
-                    radius * 10.0,
-                    Palette99::pick(i + 1).filled(),
-                );
-                chart.draw_series([circle]).unwrap();
+                let points = calc_circle_points(center, *radius);
+                let polygon = Polygon::new(points, Palette99::pick(i + 1).filled());
+                chart.draw_series([polygon])?;
+            }
+            Obstacle::PolygonObstacle { vertices } => {
+                let points: Vec<(f32, f32)> = vertices.iter().map(|v| (v[0], v[1])).collect();
+                let polygon = Polygon::new(points, Palette99::pick(i + 1).filled());
+                chart.draw_series([polygon])?;
+            }
+            Obstacle::PolytopeObstacle { normals, offsets } => {
+                let points = clip_bounds_with_half_planes(env, normals, offsets);
+                let polygon = Polygon::new(points, Palette99::pick(i + 1).filled());
+                chart.draw_series([polygon])?;
+            }
+            Obstacle::OrientedRectObstacle {
+                center,
+                size,
+                rotation,
+            } => {
+                let points: Vec<(f32, f32)> = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+                    .iter()
+                    .map(|(sx, sy)| {
+                        to_world_frame(center, rotation, (sx * size[0] / 2.0, sy * size[1] / 2.0))
+                    })
+                    .collect();
+                let polygon = Polygon::new(points, Palette99::pick(i + 1).filled());
+                chart.draw_series([polygon])?;
+            }
+            Obstacle::EllipsoidObstacle {
+                center,
+                radiuses,
+                rotation,
+            } => {
+                let points: Vec<(f32, f32)> = (0..NUM_ARC_POINTS)
+                    .map(|k| {
+                        let angle = 2.0 * PI * k as f32 / NUM_ARC_POINTS as f32;
+                        let local_point = (radiuses[0] * angle.cos(), radiuses[1] * angle.sin());
+                        to_world_frame(center, rotation, local_point)
+                    })
+                    .collect();
+                let polygon = Polygon::new(points, Palette99::pick(i + 1).filled());
+                chart.draw_series([polygon])?;
+            }
+            Obstacle::CapsuleObstacle { start, end, radius } => {
+                // Two half circles around the ends joined by straight sides
+                let heading = (end[1] - start[1]).atan2(end[0] - start[0]);
+                let mut points: Vec<(f32, f32)> = Vec::new();
+                for (point, offset) in [(end, -PI / 2.0), (start, PI / 2.0)] {
+                    for k in 0..=NUM_ARC_POINTS / 2 {
+                        let angle = heading + offset + PI * k as f32 / (NUM_ARC_POINTS / 2) as f32;
+                        points.push((
+                            point[0] + radius * angle.cos(),
+                            point[1] + radius * angle.sin(),
+                        ));
+                    }
+                }
+                let polygon = Polygon::new(points, Palette99::pick(i + 1).filled());
+                chart.draw_series([polygon])?;
+            }
+            Obstacle::UnionObstacle { .. }
+            | Obstacle::IntersectionObstacle { .. }
+            | Obstacle::DifferenceObstacle { .. }
+            | Obstacle::InflatedObstacle { .. } => {
+                let rects = rasterize_obstacle(env, obs);
+                chart.draw_series(
+                    rects
+                        .into_iter()
+                        .map(|rect| Rectangle::new(rect, Palette99::pick(i + 1).filled())),
+                )?;
             }
         };
     }
+    Ok(())
 }
 
-fn draw_path(
-    chart: &mut ChartContext<BitMapBackend, Cartesian2d<RangedCoordf32, RangedCoordf32>>,
-    path: &Vec<[f32; 2]>,
-) {
-    let path_tuple: Vec<(f32, f32)> = (0..path.len()).map(|i| (path[i][0], path[i][1])).collect();
-    chart
-        .draw_series(LineSeries::new(path_tuple.clone(), &RED))
-        .unwrap();
-
-    for i in 0..path.len() {
-        let circle = Circle::new(path_tuple[i].clone(), 3.0, Palette99::pick(0).filled());
-        chart.draw_series([circle]).unwrap();
-    }
+/// Returns the vertices of a polygon approximating the circle in data coordinates.
+pub(crate) fn calc_circle_points(center: &[f32; 2], radius: f32) -> Vec<(f32, f32)> {
+    (0..NUM_ARC_POINTS)
+        .map(|k| {
+            let angle = 2.0 * PI * k as f32 / NUM_ARC_POINTS as f32;
+            (
+                center[0] + radius * angle.cos(),
+                center[1] + radius * angle.sin(),
+            )
+        })
+        .collect()
 }
 
-fn draw_all_paths(
-    chart: &mut ChartContext<BitMapBackend, Cartesian2d<RangedCoordf32, RangedCoordf32>>,
-    nodes: &Vec<Node<2>>,
-) {
-    let circles = nodes.iter().map(|node| {
-        Circle::new(
-            (node.position[0], node.position[1]),
-            3.0,
-            Palette99::pick(0).filled(),
-        )
-    });
-    chart.draw_series(circles).unwrap();
+/// Returns rectangles covering the grid cells over the environment whose centers are inside the
+/// obstacle. Runs of adjacent cells in a row are merged into one rectangle to keep vector output
+/// small.
+fn rasterize_obstacle(env: &Env<2>, obstacle: &Obstacle<2>) -> Vec<[(f32, f32); 2]> {
+    let cell_size =
+        (env.high[0] - env.low[0]).max(env.high[1] - env.low[1]) / NUM_RASTER_CELLS as f32;
+    let num_x = ((env.high[0] - env.low[0]) / cell_size).ceil() as usize;
+    let num_y = ((env.high[1] - env.low[1]) / cell_size).ceil() as usize;
 
-    for node in nodes.iter() {
-        match node.parent {
-            Some(parent_index) => {
-                let parent_position = &nodes[parent_index].position;
-                let line = [
-                    (node.position[0], node.position[1]),
-                    (parent_position[0], parent_position[1]),
-                ];
-                chart
-                    .draw_series(LineSeries::new(line, &Palette99::pick(0)))
-                    .unwrap();
+    let mut rects = Vec::new();
+    for iy in 0..num_y {
+        let y = env.low[1] + iy as f32 * cell_size;
+        let mut run_start: Option<usize> = None;
+        for ix in 0..=num_x {
+            let x = env.low[0] + ix as f32 * cell_size;
+            let is_inside =
+                ix < num_x && obstacle.is_inside(&[x + cell_size / 2.0, y + cell_size / 2.0]);
+            match (run_start, is_inside) {
+                (None, true) => run_start = Some(ix),
+                (Some(start), false) => {
+                    let start_x = env.low[0] + start as f32 * cell_size;
+                    rects.push([(start_x, y), (x, y + cell_size)]);
+                    run_start = None;
+                }
+                _ => {}
             }
-            None => {}
         }
     }
+    rects
 }
 
-pub fn plot_env(env: &Env<2>) -> Result<(), Box<dyn std::error::Error>> {
-    let root = BitMapBackend::new("env.png", (1000, 600)).into_drawing_area();
-    root.fill(&WHITE)?;
+fn to_world_frame(center: &[f32; 2], rotation: &[[f32; 2]; 2], point: (f32, f32)) -> (f32, f32) {
+    (
+        center[0] + rotation[0][0] * point.0 + rotation[0][1] * point.1,
+        center[1] + rotation[1][0] * point.0 + rotation[1][1] * point.1,
+    )
+}
 
-    let mut chart = ChartBuilder::on(&root)
-        .margin(MARGIN)
-        .x_label_area_size(X_LABEL_AREA_SIZE)
-        .y_label_area_size(Y_LABEL_AREA_SIZE)
-        .build_cartesian_2d(env.low[0]..env.high[0], env.low[1]..env.high[1])
-        .unwrap();
-    chart.configure_mesh().draw().unwrap();
+/// Returns the vertices of the intersection of the environment bounds and the half-planes.
+fn clip_bounds_with_half_planes(
+    env: &Env<2>,
+    normals: &[[f32; 2]],
+    offsets: &[f32],
+) -> Vec<(f32, f32)> {
+    let mut points = vec![
+        (env.low[0], env.low[1]),
+        (env.high[0], env.low[1]),
+        (env.high[0], env.high[1]),
+        (env.low[0], env.high[1]),
+    ];
+
+    // Sutherland-Hodgman clipping with one half-plane at a time
+    for (normal, offset) in normals.iter().zip(offsets.iter()) {
+        let violation = |p: &(f32, f32)| normal[0] * p.0 + normal[1] * p.1 - offset;
+        let mut clipped_points = Vec::new();
+        for k in 0..points.len() {
+            let current = points[k];
+            let next = points[(k + 1) % points.len()];
+            let (v_current, v_next) = (violation(&current), violation(&next));
+            if v_current <= 0.0 {
+                clipped_points.push(current);
+            }
+            if (v_current <= 0.0) != (v_next <= 0.0) {
+                let t = v_current / (v_current - v_next);
+                clipped_points.push((
+                    current.0 + (next.0 - current.0) * t,
+                    current.1 + (next.1 - current.1) * t,
+                ));
+            }
+        }
+        points = clipped_points;
+    }
+
+    points
+}
 
-    draw_env(&mut chart, &env);
+pub(crate) fn draw_path<DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    path: &[[f32; 2]],
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let path_tuple: Vec<(f32, f32)> = path.iter().map(|p| (p[0], p[1])).collect();
+    chart.draw_series(LineSeries::new(path_tuple.clone(), &RED))?;
 
-    root.present()?;
+    let circles = path_tuple
+        .into_iter()
+        .map(|point| Circle::new(point, 3.0, Palette99::pick(0).filled()));
+    chart.draw_series(circles)?;
     Ok(())
 }
 
-pub fn plot_path(env: &Env<2>, path: &Vec<[f32; 2]>) -> Result<(), Box<dyn std::error::Error>> {
-    let root = BitMapBackend::new("path.png", (1000, 600)).into_drawing_area();
-    root.fill(&WHITE)?;
+/// Draws the edges and nodes of the search tree, with the edges labeled for a legend.
+pub(crate) fn draw_tree<DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    nodes: &[Node<2>],
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let edges = nodes.iter().filter_map(|node| {
+        node.parent.map(|parent_index| {
+            let parent_position = &nodes[parent_index].position;
+            PathElement::new(
+                vec![
+                    (node.position[0], node.position[1]),
+                    (parent_position[0], parent_position[1]),
+                ],
+                TREE_COLOR,
+            )
+        })
+    });
+    chart
+        .draw_series(edges)?
+        .label("tree")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], TREE_COLOR));
 
-    let mut chart = ChartBuilder::on(&root)
-        .margin(MARGIN)
-        .x_label_area_size(X_LABEL_AREA_SIZE)
-        .y_label_area_size(Y_LABEL_AREA_SIZE)
-        .build_cartesian_2d(env.low[0]..env.high[0], env.low[1]..env.high[1])
-        .unwrap();
-    chart.configure_mesh().draw().unwrap();
+    let circles = nodes
+        .iter()
+        .map(|node| Circle::new((node.position[0], node.position[1]), 2, TREE_COLOR.filled()));
+    chart.draw_series(circles)?;
+    Ok(())
+}
 
-    draw_env(&mut chart, &env);
-    draw_path(&mut chart, &path);
+/// Returns the ranges of the axes that cover the environment with the same scale on both axes in
+/// a chart of `size` pixels, so that shapes keep their proportions.
+fn calc_equal_aspect_ranges(env: &Env<2>, size: (u32, u32)) -> (Range<f32>, Range<f32>) {
+    let width = (size.0 as i32 - 2 * MARGIN - Y_LABEL_AREA_SIZE).max(1) as f32;
+    let height = (size.1 as i32 - 2 * MARGIN - X_LABEL_AREA_SIZE).max(1) as f32;
+    let scale = ((env.high[0] - env.low[0]) / width).max((env.high[1] - env.low[1]) / height);
 
-    root.present()?;
-    Ok(())
+    let center = (
+        (env.low[0] + env.high[0]) / 2.0,
+        (env.low[1] + env.high[1]) / 2.0,
+    );
+    let half_size = (scale * width / 2.0, scale * height / 2.0);
+    (
+        center.0 - half_size.0..center.0 + half_size.0,
+        center.1 - half_size.1..center.1 + half_size.1,
+    )
 }
 
-pub fn animate_path(
+pub(crate) fn build_chart<'a, DB: DrawingBackend>(
+    root: &'a DrawingArea<DB, Shift>,
     env: &Env<2>,
-    log: &Vec<Vec<Node<2>>>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let root = BitMapBackend::gif("log.gif", (1000, 600), 50)?.into_drawing_area();
-    let mut chart = ChartBuilder::on(&root)
+) -> Result<Chart<'a, DB>, Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let (x_range, y_range) = calc_equal_aspect_ranges(env, root.dim_in_pixel());
+    let chart = ChartBuilder::on(root)
         .margin(MARGIN)
         .x_label_area_size(X_LABEL_AREA_SIZE)
         .y_label_area_size(Y_LABEL_AREA_SIZE)
-        .build_cartesian_2d(env.low[0]..env.high[0], env.low[1]..env.high[1])
-        .unwrap();
+        .build_cartesian_2d(x_range, y_range)?;
+    Ok(chart)
+}
 
-    for nodes in log.iter() {
+/// Environment with its obstacles.
+pub struct EnvFigure<'a> {
+    pub env: &'a Env<2>,
+}
+
+impl<'a> Figure for EnvFigure<'a> {
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> Result<(), Box<dyn Error>>
+    where
+        DB::ErrorType: 'static,
+    {
         root.fill(&WHITE)?;
-        chart.configure_mesh().draw().unwrap();
+        let mut chart = build_chart(root, self.env)?;
+        chart.configure_mesh().draw()?;
 
-        draw_env(&mut chart, &env);
-        draw_all_paths(&mut chart, &nodes);
+        draw_env(&mut chart, self.env)?;
+        Ok(())
+    }
+}
 
-        root.present()?;
+/// Environment with a path through it.
+pub struct PathFigure<'a> {
+    pub env: &'a Env<2>,
+    pub path: &'a [[f32; 2]],
+}
+
+impl<'a> Figure for PathFigure<'a> {
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> Result<(), Box<dyn Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+        let mut chart = build_chart(root, self.env)?;
+        chart.configure_mesh().draw()?;
+
+        draw_env(&mut chart, self.env)?;
+        draw_path(&mut chart, self.path)?;
+        Ok(())
     }
-    Ok(())
+}
+
+/// Saves the environment to `env.png`.
+pub fn plot_env(env: &Env<2>) -> Result<(), Box<dyn Error>> {
+    EnvFigure { env }.save("env.png", DEFAULT_SIZE)
+}
+
+/// Saves the environment and the path to `path.png`.
+pub fn plot_path(env: &Env<2>, path: &[[f32; 2]]) -> Result<(), Box<dyn Error>> {
+    PathFigure { env, path }.save("path.png", DEFAULT_SIZE)
 }
@@ -0,0 +1,231 @@
+use crate::env::Env;
+use crate::planner::Node;
+use crate::plot::mesh::{create_obstacle_mesh, dot};
+use crate::plot::plot2d::TREE_COLOR;
+use crate::plot::plot_builder::MARKER_SIZE;
+use crate::plot::Figure;
+use crate::plot::PATH_COLORS;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::cmp::Reverse;
+use std::error::Error;
+use std::ops::Range;
+
+const MARGIN: i32 = 20;
+/// Direction towards the light used to shade the faces of obstacles.
+const LIGHT_DIRECTION: [f32; 3] = [0.32, 0.48, 0.82];
+
+/// Depth, vertices in chart coordinates and colour of an obstacle face.
+type ShadedFace = (i32, Vec<(f32, f32, f32)>, RGBColor);
+
+struct LabeledPath<'a> {
+    waypoints: &'a [[f32; 3]],
+    label: String,
+    color: RGBColor,
+}
+
+/// Projected view of a 3D environment with any of a search tree, paths and start and goal markers.
+/// The z axis is drawn upwards.
+pub struct PlotBuilder3d<'a> {
+    env: &'a Env<3>,
+    tree: Option<&'a [Node<3>]>,
+    paths: Vec<LabeledPath<'a>>,
+    start: Option<[f32; 3]>,
+    goal: Option<[f32; 3]>,
+    is_legend_shown: bool,
+    yaw: f64,
+    pitch: f64,
+}
+
+impl<'a> PlotBuilder3d<'a> {
+    pub fn new(env: &'a Env<3>) -> Self {
+        PlotBuilder3d {
+            env,
+            tree: None,
+            paths: Vec::new(),
+            start: None,
+            goal: None,
+            is_legend_shown: true,
+            yaw: 0.5,
+            pitch: 0.3,
+        }
+    }
+
+    pub fn tree(mut self, nodes: &'a [Node<3>]) -> Self {
+        self.tree = Some(nodes);
+        self
+    }
+
+    /// Adds a path drawn in the next colour of `PATH_COLORS`.
+    pub fn path(self, waypoints: &'a [[f32; 3]], label: &str) -> Self {
+        let color = PATH_COLORS[self.paths.len() % PATH_COLORS.len()];
+        self.path_with_color(waypoints, label, color)
+    }
+
+    pub fn path_with_color(
+        mut self,
+        waypoints: &'a [[f32; 3]],
+        label: &str,
+        color: RGBColor,
+    ) -> Self {
+        self.paths.push(LabeledPath {
+            waypoints,
+            label: label.to_string(),
+            color,
+        });
+        self
+    }
+
+    pub fn start(mut self, start: [f32; 3]) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    pub fn goal(mut self, goal: [f32; 3]) -> Self {
+        self.goal = Some(goal);
+        self
+    }
+
+    pub fn legend(mut self, is_legend_shown: bool) -> Self {
+        self.is_legend_shown = is_legend_shown;
+        self
+    }
+
+    /// Sets the rotation of the view around the vertical axis and its tilt, in radians.
+    pub fn view(mut self, yaw: f64, pitch: f64) -> Self {
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self
+    }
+}
+
+/// Returns the position in the chart, whose second axis is the vertical one.
+fn to_chart(position: &[f32; 3]) -> (f32, f32, f32) {
+    (position[0], position[2], position[1])
+}
+
+/// Returns the ranges of the x, z and y axes with the same scale, covering the environment.
+fn calc_cube_ranges(env: &Env<3>) -> (Range<f32>, Range<f32>, Range<f32>) {
+    let half_size = (0..3)
+        .map(|i| (env.high[i] - env.low[i]) / 2.0)
+        .fold(0.0, f32::max);
+    let range = |i: usize| {
+        let center = (env.low[i] + env.high[i]) / 2.0;
+        center - half_size..center + half_size
+    };
+    (range(0), range(2), range(1))
+}
+
+impl<'a> Figure for PlotBuilder3d<'a> {
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> Result<(), Box<dyn Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+        let (x_range, y_range, z_range) = calc_cube_ranges(self.env);
+        let mut chart = ChartBuilder::on(root)
+            .margin(MARGIN)
+            .build_cartesian_3d(x_range, y_range, z_range)?;
+        chart.with_projection(|mut projection| {
+            projection.yaw = self.yaw;
+            projection.pitch = self.pitch;
+            projection.scale = 0.9;
+            projection.into_matrix()
+        });
+        chart.configure_axes().draw()?;
+
+        // Faces are drawn from the farthest to the nearest so that nearer faces cover farther ones
+        let mut faces: Vec<ShadedFace> = Vec::new();
+        for (i, obstacle) in self.env.get_obstacles().iter().enumerate() {
+            let mesh = create_obstacle_mesh(self.env, obstacle);
+            let color = Palette99::pick(i + 1).to_rgba();
+            for (k, face) in mesh.faces.iter().enumerate() {
+                let points: Vec<(f32, f32, f32)> = face
+                    .iter()
+                    .map(|vertex_index| to_chart(&mesh.vertices[*vertex_index]))
+                    .collect();
+                let depth = points
+                    .iter()
+                    .map(|(x, y, z)| chart.as_coord_spec().projected_depth(x, y, z))
+                    .sum();
+                let brightness =
+                    0.55 + 0.45 * dot(&mesh.calc_face_normal(k), &LIGHT_DIRECTION).abs();
+                let shade = |c: u8| (c as f32 * brightness) as u8;
+                let shaded_color = RGBColor(shade(color.0), shade(color.1), shade(color.2));
+                faces.push((depth, points, shaded_color));
+            }
+        }
+        faces.sort_by_key(|face| Reverse(face.0));
+        chart.draw_series(
+            faces
+                .into_iter()
+                .map(|(_, points, color)| Polygon::new(points, color.filled())),
+        )?;
+
+        let mut has_labels = false;
+        if let Some(nodes) = self.tree {
+            let edges = nodes.iter().filter_map(|node| {
+                node.parent.map(|parent_index| {
+                    PathElement::new(
+                        vec![
+                            to_chart(&node.position),
+                            to_chart(&nodes[parent_index].position),
+                        ],
+                        TREE_COLOR,
+                    )
+                })
+            });
+            chart
+                .draw_series(edges)?
+                .label("tree")
+                .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], TREE_COLOR));
+            has_labels = true;
+        }
+
+        for path in self.paths.iter() {
+            let color = path.color;
+            let points: Vec<(f32, f32, f32)> = path.waypoints.iter().map(to_chart).collect();
+            chart
+                .draw_series(LineSeries::new(points.clone(), color.stroke_width(2)))?
+                .label(path.label.as_str())
+                .legend(move |(x, y)| {
+                    PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2))
+                });
+            chart.draw_series(
+                points
+                    .into_iter()
+                    .map(|point| Circle::new(point, 3, color.filled())),
+            )?;
+            has_labels = true;
+        }
+
+        if let Some(start) = self.start {
+            chart
+                .draw_series([Circle::new(to_chart(&start), MARKER_SIZE, GREEN.filled())])?
+                .label("start")
+                .legend(|(x, y)| Circle::new((x + 10, y), MARKER_SIZE, GREEN.filled()));
+            has_labels = true;
+        }
+        if let Some(goal) = self.goal {
+            chart
+                .draw_series([Cross::new(
+                    to_chart(&goal),
+                    MARKER_SIZE,
+                    BLACK.stroke_width(3),
+                )])?
+                .label("goal")
+                .legend(|(x, y)| Cross::new((x + 10, y), MARKER_SIZE, BLACK.stroke_width(3)));
+            has_labels = true;
+        }
+
+        if self.is_legend_shown && has_labels {
+            chart
+                .configure_series_labels()
+                .position(SeriesLabelPosition::UpperLeft)
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()?;
+        }
+        Ok(())
+    }
+}
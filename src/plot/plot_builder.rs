@@ -0,0 +1,214 @@
+use crate::env::Env;
+use crate::planner::Node;
+use crate::plot::plot2d::{build_chart, draw_env, draw_tree};
+use crate::plot::Figure;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::error::Error;
+use std::f32::consts::PI;
+
+const NUM_ELLIPSE_POINTS: usize = 64;
+pub(crate) const MARKER_SIZE: i32 = 6;
+pub(crate) const ELLIPSE_COLOR: RGBColor = RGBColor(120, 120, 120);
+
+/// Colours given in turn to paths added without an explicit colour.
+pub const PATH_COLORS: [RGBColor; 6] = [RED, BLUE, GREEN, MAGENTA, CYAN, RGBColor(255, 140, 0)];
+
+struct LabeledPath<'a> {
+    waypoints: &'a [[f32; 2]],
+    label: String,
+    color: RGBColor,
+}
+
+/// Figure overlaying any of a search tree, paths, start and goal markers and the informed ellipse
+/// on the environment, with a legend of the overlays.
+pub struct PlotBuilder<'a> {
+    env: &'a Env<2>,
+    tree: Option<&'a [Node<2>]>,
+    paths: Vec<LabeledPath<'a>>,
+    start: Option<[f32; 2]>,
+    goal: Option<[f32; 2]>,
+    informed_ellipse: Option<([f32; 2], [f32; 2], f32)>,
+    is_legend_shown: bool,
+    legend_position: SeriesLabelPosition,
+}
+
+impl<'a> PlotBuilder<'a> {
+    pub fn new(env: &'a Env<2>) -> Self {
+        PlotBuilder {
+            env,
+            tree: None,
+            paths: Vec::new(),
+            start: None,
+            goal: None,
+            informed_ellipse: None,
+            is_legend_shown: true,
+            legend_position: SeriesLabelPosition::UpperLeft,
+        }
+    }
+
+    /// Draws the edges and nodes of the search tree under the paths.
+    pub fn tree(mut self, nodes: &'a [Node<2>]) -> Self {
+        self.tree = Some(nodes);
+        self
+    }
+
+    /// Adds a path drawn in the next colour of `PATH_COLORS`.
+    pub fn path(self, waypoints: &'a [[f32; 2]], label: &str) -> Self {
+        let color = PATH_COLORS[self.paths.len() % PATH_COLORS.len()];
+        self.path_with_color(waypoints, label, color)
+    }
+
+    pub fn path_with_color(
+        mut self,
+        waypoints: &'a [[f32; 2]],
+        label: &str,
+        color: RGBColor,
+    ) -> Self {
+        self.paths.push(LabeledPath {
+            waypoints,
+            label: label.to_string(),
+            color,
+        });
+        self
+    }
+
+    pub fn start(mut self, start: [f32; 2]) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    pub fn goal(mut self, goal: [f32; 2]) -> Self {
+        self.goal = Some(goal);
+        self
+    }
+
+    /// Draws the ellipse of the positions that can improve a solution of cost `c_best` from
+    /// `start` to `goal`, from which Informed RRT* samples.
+    pub fn informed_ellipse(mut self, start: [f32; 2], goal: [f32; 2], c_best: f32) -> Self {
+        self.informed_ellipse = Some((start, goal, c_best));
+        self
+    }
+
+    pub fn legend(mut self, is_legend_shown: bool) -> Self {
+        self.is_legend_shown = is_legend_shown;
+        self
+    }
+
+    pub fn legend_position(mut self, legend_position: SeriesLabelPosition) -> Self {
+        self.legend_position = legend_position;
+        self
+    }
+}
+
+impl<'a> Figure for PlotBuilder<'a> {
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> Result<(), Box<dyn Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+        let mut chart = build_chart(root, self.env)?;
+        chart.configure_mesh().draw()?;
+
+        draw_env(&mut chart, self.env)?;
+
+        let mut has_labels = false;
+        if let Some(nodes) = self.tree {
+            draw_tree(&mut chart, nodes)?;
+            has_labels = true;
+        }
+
+        if let Some((start, goal, c_best)) = self.informed_ellipse {
+            let mut points = calc_informed_ellipse_points(&start, &goal, c_best);
+            if !points.is_empty() {
+                points.push(points[0]);
+                chart
+                    .draw_series([PathElement::new(points, ELLIPSE_COLOR.stroke_width(2))])?
+                    .label("informed ellipse")
+                    .legend(|(x, y)| {
+                        PathElement::new(vec![(x, y), (x + 20, y)], ELLIPSE_COLOR.stroke_width(2))
+                    });
+                has_labels = true;
+            }
+        }
+
+        for path in self.paths.iter() {
+            let color = path.color;
+            let points: Vec<(f32, f32)> = path.waypoints.iter().map(|p| (p[0], p[1])).collect();
+            chart
+                .draw_series(LineSeries::new(points.clone(), color.stroke_width(2)))?
+                .label(path.label.as_str())
+                .legend(move |(x, y)| {
+                    PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2))
+                });
+            chart.draw_series(
+                points
+                    .into_iter()
+                    .map(|point| Circle::new(point, 3, color.filled())),
+            )?;
+            has_labels = true;
+        }
+
+        if let Some(start) = self.start {
+            chart
+                .draw_series([Circle::new(
+                    (start[0], start[1]),
+                    MARKER_SIZE,
+                    GREEN.filled(),
+                )])?
+                .label("start")
+                .legend(|(x, y)| Circle::new((x + 10, y), MARKER_SIZE, GREEN.filled()));
+            has_labels = true;
+        }
+        if let Some(goal) = self.goal {
+            chart
+                .draw_series([Cross::new(
+                    (goal[0], goal[1]),
+                    MARKER_SIZE,
+                    BLACK.stroke_width(3),
+                )])?
+                .label("goal")
+                .legend(|(x, y)| Cross::new((x + 10, y), MARKER_SIZE, BLACK.stroke_width(3)));
+            has_labels = true;
+        }
+
+        if self.is_legend_shown && has_labels {
+            chart
+                .configure_series_labels()
+                .position(self.legend_position.clone())
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the boundary of the ellipse with foci `start` and `goal` whose points have a sum of
+/// distances to the foci of `c_best`, or no points if `c_best` is not finite or too small.
+pub(crate) fn calc_informed_ellipse_points(
+    start: &[f32; 2],
+    goal: &[f32; 2],
+    c_best: f32,
+) -> Vec<(f32, f32)> {
+    let (dx, dy) = (goal[0] - start[0], goal[1] - start[1]);
+    let c_min = (dx * dx + dy * dy).sqrt();
+    if !c_best.is_finite() || c_best < c_min {
+        return Vec::new();
+    }
+
+    let center = ((start[0] + goal[0]) / 2.0, (start[1] + goal[1]) / 2.0);
+    let (semi_major_axis, semi_minor_axis) =
+        (c_best / 2.0, (c_best.powi(2) - c_min.powi(2)).sqrt() / 2.0);
+    let heading = dy.atan2(dx);
+    (0..NUM_ELLIPSE_POINTS)
+        .map(|k| {
+            let angle = 2.0 * PI * k as f32 / NUM_ELLIPSE_POINTS as f32;
+            let (x, y) = (semi_major_axis * angle.cos(), semi_minor_axis * angle.sin());
+            (
+                center.0 + heading.cos() * x - heading.sin() * y,
+                center.1 + heading.sin() * x + heading.cos() * y,
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,5 @@
+mod scenario;
+pub use crate::scenario::scenario::PlannerConfig;
+pub use crate::scenario::scenario::PlannerKind;
+pub use crate::scenario::scenario::Query;
+pub use crate::scenario::scenario::Scenario;
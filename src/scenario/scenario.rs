@@ -0,0 +1,171 @@
+use crate::env::Env;
+use crate::env::Obstacle;
+use crate::planner::AbstractRRT;
+use crate::planner::InformedRRTStar;
+use crate::planner::RRTStar;
+use crate::planner::RRT;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlannerKind {
+    #[serde(rename = "rrt")]
+    RRT,
+    #[serde(rename = "rrtstar")]
+    RRTStar,
+    #[serde(rename = "informed-rrtstar")]
+    InformedRRTStar,
+}
+
+impl PlannerKind {
+    pub fn from_name(name: &str) -> Option<PlannerKind> {
+        match name {
+            "rrt" => Some(PlannerKind::RRT),
+            "rrtstar" => Some(PlannerKind::RRTStar),
+            "informed-rrtstar" => Some(PlannerKind::InformedRRTStar),
+            _ => None,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            PlannerKind::RRT => "rrt",
+            PlannerKind::RRTStar => "rrtstar",
+            PlannerKind::InformedRRTStar => "informed-rrtstar",
+        }
+    }
+}
+
+fn default_goal_sample_rate() -> f32 {
+    0.2
+}
+
+fn default_step_size() -> f32 {
+    2.0
+}
+
+fn default_max_iter() -> usize {
+    2000
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlannerConfig {
+    #[serde(rename = "type")]
+    pub kind: PlannerKind,
+    #[serde(default = "default_goal_sample_rate")]
+    pub goal_sample_rate: f32,
+    #[serde(default = "default_step_size")]
+    pub step_size: f32,
+    #[serde(default = "default_max_iter")]
+    pub max_iter: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Query<const D: usize> {
+    #[serde(with = "crate::serde_array::array")]
+    pub start: [f32; D],
+    #[serde(with = "crate::serde_array::array")]
+    pub goal: [f32; D],
+}
+
+/// Planning problem stored as data: the bounds and obstacles of the environment, the start and
+/// goal pairs to plan between and the planner to use.
+#[derive(Serialize, Deserialize)]
+pub struct Scenario<const D: usize> {
+    #[serde(with = "crate::serde_array::array")]
+    pub low: [f32; D],
+    #[serde(with = "crate::serde_array::array")]
+    pub high: [f32; D],
+    #[serde(default)]
+    pub obstacles: Vec<Obstacle<D>>,
+    pub queries: Vec<Query<D>>,
+    pub planner: PlannerConfig,
+}
+
+impl<const D: usize> Scenario<D> {
+    pub fn from_json_str(s: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    pub fn from_toml_str(s: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Loads a scenario from a `.json` or `.toml` file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let s = fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Scenario::from_json_str(&s),
+            Some("toml") => Scenario::from_toml_str(&s),
+            _ => Err(format!("unsupported scenario file: {}", path.display()).into()),
+        }
+    }
+
+    pub fn to_json_string(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn to_toml_string(&self) -> Result<String, Box<dyn Error>> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Returns the environment of the scenario with its bounding volume hierarchy built.
+    pub fn create_env(&self) -> Env<D> {
+        let mut env = Env::new(self.low, self.high, self.obstacles.to_vec());
+        env.build_bvh();
+        env
+    }
+
+    /// Returns the planner configured for the `index`-th query, rejecting positions inside the
+    /// obstacles of `env`.
+    pub fn create_planner(&self, env: &Env<D>, index: usize) -> Box<dyn AbstractRRT<D>> {
+        let query = &self.queries[index];
+        let config = &self.planner;
+
+        let _env = env.clone();
+        let is_approved = Box::new(move |position: &[f32; D]| !_env.is_inside_obstacle(position));
+
+        match config.kind {
+            PlannerKind::RRT => Box::new(RRT::new(
+                query.start,
+                query.goal,
+                self.low,
+                self.high,
+                is_approved,
+                config.goal_sample_rate,
+                config.step_size,
+                config.max_iter,
+            )),
+            PlannerKind::RRTStar => Box::new(RRTStar::new(
+                query.start,
+                query.goal,
+                self.low,
+                self.high,
+                is_approved,
+                config.goal_sample_rate,
+                config.step_size,
+                config.max_iter,
+            )),
+            PlannerKind::InformedRRTStar => Box::new(InformedRRTStar::new(
+                query.start,
+                query.goal,
+                self.low,
+                self.high,
+                is_approved,
+                config.goal_sample_rate,
+                config.step_size,
+                config.max_iter,
+            )),
+        }
+    }
+
+    /// Returns a planner for each query.
+    pub fn create_planners(&self, env: &Env<D>) -> Vec<Box<dyn AbstractRRT<D>>> {
+        (0..self.queries.len())
+            .map(|index| self.create_planner(env, index))
+            .collect()
+    }
+}
@@ -0,0 +1,118 @@
+//! Helpers for `#[serde(with = ...)]` on const generic arrays, which serde does not support
+//! out of the box. Arrays are written as sequences, e.g. `[1.0, 2.0]` in JSON.
+
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+struct ArrayRef<'a, T, const D: usize>(&'a [T; D]);
+
+impl<'a, T: Serialize, const D: usize> Serialize for ArrayRef<'a, T, D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(D)?;
+        for element in self.0.iter() {
+            tuple.serialize_element(element)?;
+        }
+        tuple.end()
+    }
+}
+
+struct Array<T, const D: usize>([T; D]);
+
+struct ArrayVisitor<T, const D: usize>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>, const D: usize> Visitor<'de> for ArrayVisitor<T, D> {
+    type Value = Array<T, D>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an array of length {}", D)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut elements: Vec<T> = Vec::with_capacity(D);
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+        let len = elements.len();
+        elements
+            .try_into()
+            .map(Array)
+            .map_err(|_| A::Error::invalid_length(len, &self))
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const D: usize> Deserialize<'de> for Array<T, D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        deserializer.deserialize_tuple(D, ArrayVisitor(PhantomData))
+    }
+}
+
+/// `[T; D]`
+pub mod array {
+    use super::{Array, ArrayRef};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, T: Serialize, const D: usize>(
+        array: &[T; D],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ArrayRef(array).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, De: Deserializer<'de>, T: Deserialize<'de>, const D: usize>(
+        deserializer: De,
+    ) -> Result<[T; D], De::Error> {
+        Array::deserialize(deserializer).map(|array| array.0)
+    }
+}
+
+/// `[[T; D]; D]`, written row by row.
+pub mod matrix {
+    use super::{Array, ArrayRef};
+    use serde::ser::SerializeTuple;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, T: Serialize, const D: usize>(
+        matrix: &[[T; D]; D],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(D)?;
+        for row in matrix.iter() {
+            tuple.serialize_element(&ArrayRef(row))?;
+        }
+        tuple.end()
+    }
+
+    pub fn deserialize<'de, De: Deserializer<'de>, T: Deserialize<'de>, const D: usize>(
+        deserializer: De,
+    ) -> Result<[[T; D]; D], De::Error> {
+        let rows: Array<Array<T, D>, D> = Array::deserialize(deserializer)?;
+        Ok(rows.0.map(|row| row.0))
+    }
+}
+
+/// `Vec<[T; D]>`
+pub mod vec_array {
+    use super::{Array, ArrayRef};
+    use serde::ser::SerializeSeq;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, T: Serialize, const D: usize>(
+        arrays: &[[T; D]],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(arrays.len()))?;
+        for array in arrays.iter() {
+            seq.serialize_element(&ArrayRef(array))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, De: Deserializer<'de>, T: Deserialize<'de>, const D: usize>(
+        deserializer: De,
+    ) -> Result<Vec<[T; D]>, De::Error> {
+        let arrays: Vec<Array<T, D>> = Vec::deserialize(deserializer)?;
+        Ok(arrays.into_iter().map(|array| array.0).collect())
+    }
+}
@@ -0,0 +1,9 @@
+mod spline;
+mod time_parameterization;
+pub use crate::trajectory::spline::is_curve_collision_free;
+pub use crate::trajectory::spline::BSpline;
+pub use crate::trajectory::spline::CubicSpline;
+pub use crate::trajectory::spline::Curve;
+pub use crate::trajectory::time_parameterization::parameterize_time_optimal;
+pub use crate::trajectory::time_parameterization::parameterize_trapezoidal;
+pub use crate::trajectory::time_parameterization::Trajectory;
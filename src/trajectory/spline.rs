@@ -0,0 +1,209 @@
+use crate::env::Env;
+
+pub trait Curve<const D: usize> {
+    fn get_max_parameter(&self) -> f32;
+    fn evaluate(&self, t: f32) -> [f32; D];
+
+    fn sample(&self, num_samples: usize) -> Vec<[f32; D]> {
+        let num_samples = num_samples.max(2);
+        (0..num_samples)
+            .map(|k| self.evaluate(self.get_max_parameter() * k as f32 / (num_samples - 1) as f32))
+            .collect()
+    }
+}
+
+/// Checks the curve against the environment at points spaced at most `resolution` apart.
+pub fn is_curve_collision_free<const D: usize>(
+    curve: &dyn Curve<D>,
+    env: &Env<D>,
+    resolution: f32,
+) -> bool {
+    // Estimate the curve length from a coarse polyline to choose the number of checks
+    let coarse_points = curve.sample(100);
+    let length = coarse_points
+        .windows(2)
+        .map(|w| calc_distance(&w[0], &w[1]))
+        .sum::<f32>();
+    let num_samples = (length / resolution).ceil() as usize + 1;
+
+    curve.sample(num_samples).iter().all(|position| {
+        (0..D).all(|i| env.low[i] <= position[i] && position[i] <= env.high[i])
+            && !env.is_inside_obstacle(position)
+    })
+}
+
+fn calc_distance<const D: usize>(from: &[f32; D], to: &[f32; D]) -> f32 {
+    (0..D)
+        .map(|i| (to[i] - from[i]).powf(2.0))
+        .sum::<f32>()
+        .powf(0.5)
+}
+
+/// Interpolating natural cubic spline parameterized by the cumulative chord length.
+pub struct CubicSpline<const D: usize> {
+    pub knots: Vec<f32>,
+    pub points: Vec<[f32; D]>,
+    second_derivatives: Vec<[f32; D]>,
+}
+
+impl<const D: usize> CubicSpline<D> {
+    pub fn new(waypoints: &[[f32; D]]) -> Self {
+        // Drop consecutive duplicates so that the knots are strictly increasing
+        let mut points: Vec<[f32; D]> = Vec::new();
+        for waypoint in waypoints.iter() {
+            match points.last() {
+                Some(last) if calc_distance(last, waypoint) == 0.0 => {}
+                _ => points.push(*waypoint),
+            }
+        }
+
+        let mut knots = vec![0.0];
+        for k in 1..points.len() {
+            knots.push(knots[k - 1] + calc_distance(&points[k - 1], &points[k]));
+        }
+
+        let second_derivatives = calc_second_derivatives(&knots, &points);
+        CubicSpline {
+            knots,
+            points,
+            second_derivatives,
+        }
+    }
+}
+
+fn calc_second_derivatives<const D: usize>(knots: &[f32], points: &[[f32; D]]) -> Vec<[f32; D]> {
+    let n = points.len();
+    let mut m: Vec<[f32; D]> = vec![[0.0; D]; n];
+    if n < 3 {
+        return m;
+    }
+
+    // Solve the tridiagonal system of the natural spline with the Thomas algorithm
+    let h: Vec<f32> = (0..n - 1).map(|k| knots[k + 1] - knots[k]).collect();
+    let mut c_prime: Vec<f32> = vec![0.0; n];
+    let mut d_prime: Vec<[f32; D]> = vec![[0.0; D]; n];
+    for k in 1..n - 1 {
+        let a = h[k - 1];
+        let b = 2.0 * (h[k - 1] + h[k]);
+        let c = h[k];
+        let denominator = b - a * c_prime[k - 1];
+        c_prime[k] = c / denominator;
+        for i in 0..D {
+            let rhs = 6.0
+                * ((points[k + 1][i] - points[k][i]) / h[k]
+                    - (points[k][i] - points[k - 1][i]) / h[k - 1]);
+            d_prime[k][i] = (rhs - a * d_prime[k - 1][i]) / denominator;
+        }
+    }
+
+    for k in (1..n - 1).rev() {
+        for i in 0..D {
+            m[k][i] = d_prime[k][i] - c_prime[k] * m[k + 1][i];
+        }
+    }
+
+    m
+}
+
+impl<const D: usize> Curve<D> for CubicSpline<D> {
+    fn get_max_parameter(&self) -> f32 {
+        *self.knots.last().unwrap_or(&0.0)
+    }
+
+    fn evaluate(&self, t: f32) -> [f32; D] {
+        if self.points.len() < 2 {
+            return self.points.first().copied().unwrap_or([0.0; D]);
+        }
+
+        let t = t.clamp(0.0, self.get_max_parameter());
+        let k = match self.knots.iter().rposition(|&knot| knot <= t) {
+            Some(k) => k.min(self.points.len() - 2),
+            None => 0,
+        };
+
+        let h = self.knots[k + 1] - self.knots[k];
+        let a = (self.knots[k + 1] - t) / h;
+        let b = (t - self.knots[k]) / h;
+
+        let mut position: [f32; D] = [0.0; D];
+        for (i, value) in position.iter_mut().enumerate() {
+            let m_0 = self.second_derivatives[k][i];
+            let m_1 = self.second_derivatives[k + 1][i];
+            *value = a * self.points[k][i]
+                + b * self.points[k + 1][i]
+                + ((a.powf(3.0) - a) * m_0 + (b.powf(3.0) - b) * m_1) * h.powf(2.0) / 6.0;
+        }
+
+        position
+    }
+}
+
+/// Clamped uniform B-spline that uses the waypoints as control points.
+///
+/// The curve starts and ends at the first and last waypoints and stays inside the convex hull of
+/// the control polygon, but does not pass through the inner waypoints.
+pub struct BSpline<const D: usize> {
+    pub control_points: Vec<[f32; D]>,
+    pub degree: usize,
+    knots: Vec<f32>,
+}
+
+impl<const D: usize> BSpline<D> {
+    pub fn new(control_points: &[[f32; D]], degree: usize) -> Self {
+        let n = control_points.len();
+        let degree = degree.min(n.saturating_sub(1));
+
+        let num_inner_knots = n.saturating_sub(degree + 1);
+        let mut knots: Vec<f32> = vec![0.0; degree + 1];
+        for k in 1..=num_inner_knots {
+            knots.push(k as f32 / (num_inner_knots + 1) as f32);
+        }
+        knots.extend(vec![1.0; degree + 1]);
+
+        BSpline {
+            control_points: control_points.to_vec(),
+            degree,
+            knots,
+        }
+    }
+}
+
+impl<const D: usize> Curve<D> for BSpline<D> {
+    fn get_max_parameter(&self) -> f32 {
+        1.0
+    }
+
+    fn evaluate(&self, t: f32) -> [f32; D] {
+        let n = self.control_points.len();
+        if n < 2 {
+            return self.control_points.first().copied().unwrap_or([0.0; D]);
+        }
+
+        // Find the knot span and run the de Boor algorithm
+        let t = t.clamp(0.0, 1.0);
+        let p = self.degree;
+        let mut span = p;
+        while span < n - 1 && self.knots[span + 1] <= t {
+            span += 1;
+        }
+
+        let mut d: Vec<[f32; D]> = (0..=p).map(|j| self.control_points[j + span - p]).collect();
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let left = self.knots[j + span - p];
+                let right = self.knots[j + 1 + span - r];
+                let alpha = if right > left {
+                    (t - left) / (right - left)
+                } else {
+                    0.0
+                };
+                let previous = d[j - 1];
+                for (value, previous_value) in d[j].iter_mut().zip(previous) {
+                    *value = (1.0 - alpha) * previous_value + alpha * *value;
+                }
+            }
+        }
+
+        d[p]
+    }
+}
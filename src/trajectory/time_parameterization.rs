@@ -0,0 +1,290 @@
+use std::error::Error;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trajectory<const D: usize> {
+    pub times: Vec<f32>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::vec_array"))]
+    pub positions: Vec<[f32; D]>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array::vec_array"))]
+    pub velocities: Vec<[f32; D]>,
+}
+
+impl<const D: usize> Trajectory<D> {
+    pub fn get_duration(&self) -> f32 {
+        *self.times.last().unwrap_or(&0.0)
+    }
+
+    /// Returns the position at time `t` by linear interpolation between the samples.
+    pub fn get_position(&self, t: f32) -> [f32; D] {
+        if self.positions.is_empty() {
+            return [0.0; D];
+        }
+
+        let k = self.times.partition_point(|&time| time <= t);
+        if k == 0 {
+            return self.positions[0];
+        }
+        if k == self.times.len() {
+            return self.positions[k - 1];
+        }
+
+        let ratio = (t - self.times[k - 1]) / (self.times[k] - self.times[k - 1]);
+        let mut position: [f32; D] = [0.0; D];
+        for (i, value) in position.iter_mut().enumerate() {
+            *value = self.positions[k - 1][i]
+                + ratio * (self.positions[k][i] - self.positions[k - 1][i]);
+        }
+
+        position
+    }
+}
+
+/// Returns an error unless every limit is positive and finite.
+fn check_limits<const D: usize>(
+    max_velocity: &[f32; D],
+    max_acceleration: &[f32; D],
+) -> Result<(), Box<dyn Error>> {
+    let is_valid = |limit: &f32| *limit > 0.0 && limit.is_finite();
+    if !max_velocity.iter().all(is_valid) || !max_acceleration.iter().all(is_valid) {
+        return Err("the velocity and acceleration limits must be positive and finite".into());
+    }
+    Ok(())
+}
+
+fn calc_arc_lengths<const D: usize>(points: &[[f32; D]]) -> Vec<f32> {
+    let mut arc_lengths = vec![0.0];
+    for k in 1..points.len() {
+        let distance = (0..D)
+            .map(|i| (points[k][i] - points[k - 1][i]).powf(2.0))
+            .sum::<f32>()
+            .powf(0.5);
+        arc_lengths.push(arc_lengths[k - 1] + distance);
+    }
+    arc_lengths
+}
+
+fn calc_tangent<const D: usize>(points: &[[f32; D]], arc_lengths: &[f32], k: usize) -> [f32; D] {
+    let n = points.len();
+    let mut tangent: [f32; D] = [0.0; D];
+    if n < 2 {
+        return tangent;
+    }
+
+    let (from, to) = if k == 0 {
+        (0, 1)
+    } else if k == n - 1 {
+        (n - 2, n - 1)
+    } else {
+        (k - 1, k + 1)
+    };
+
+    let ds = arc_lengths[to] - arc_lengths[from];
+    if ds > 0.0 {
+        for i in 0..D {
+            tangent[i] = (points[to][i] - points[from][i]) / ds;
+        }
+    }
+    tangent
+}
+
+fn build_trajectory<const D: usize>(
+    points: &[[f32; D]],
+    arc_lengths: &[f32],
+    speeds: &[f32],
+) -> Trajectory<D> {
+    let mut times = vec![0.0];
+    for k in 1..points.len() {
+        let ds = arc_lengths[k] - arc_lengths[k - 1];
+        let mean_speed = (speeds[k - 1] + speeds[k]) / 2.0;
+        let dt = if mean_speed > 0.0 {
+            ds / mean_speed
+        } else {
+            0.0
+        };
+        times.push(times[k - 1] + dt);
+    }
+
+    let velocities = (0..points.len())
+        .map(|k| {
+            let tangent = calc_tangent(points, arc_lengths, k);
+            let mut velocity: [f32; D] = [0.0; D];
+            for i in 0..D {
+                velocity[i] = tangent[i] * speeds[k];
+            }
+            velocity
+        })
+        .collect();
+
+    Trajectory {
+        times,
+        positions: points.to_vec(),
+        velocities,
+    }
+}
+
+/// Assigns a rest-to-rest trapezoidal speed profile along the arc length of `points`.
+///
+/// The scalar limits are the smallest per-axis limits, which keeps every axis within its limits
+/// regardless of the direction of motion. Samples are inserted where the profile switches between
+/// accelerating, cruising and decelerating, so the acceleration between consecutive samples is
+/// constant and the limits hold for sparse paths as well. Fails unless every limit is positive and
+/// finite.
+pub fn parameterize_trapezoidal<const D: usize>(
+    points: &[[f32; D]],
+    max_velocity: [f32; D],
+    max_acceleration: [f32; D],
+) -> Result<Trajectory<D>, Box<dyn Error>> {
+    check_limits(&max_velocity, &max_acceleration)?;
+    if points.len() < 2 {
+        let zeros = vec![0.0; points.len()];
+        return Ok(build_trajectory(points, &zeros, &zeros));
+    }
+
+    let velocity = max_velocity.iter().cloned().fold(f32::MAX, f32::min);
+    let acceleration = max_acceleration.iter().cloned().fold(f32::MAX, f32::min);
+
+    let arc_lengths = calc_arc_lengths(points);
+    let length = arc_lengths[arc_lengths.len() - 1];
+
+    // Reduce the peak speed when there is no room for a cruise phase
+    let peak_velocity = velocity.min((acceleration * length).powf(0.5));
+    let ramp_length = peak_velocity.powf(2.0) / (2.0 * acceleration);
+
+    let (points, arc_lengths) =
+        insert_phase_boundaries(points, &arc_lengths, &[ramp_length, length - ramp_length]);
+
+    let speeds: Vec<f32> = arc_lengths
+        .iter()
+        .map(|&s| {
+            let s_from_end = (length - s).max(0.0);
+            if s < ramp_length {
+                (2.0 * acceleration * s).powf(0.5)
+            } else if s_from_end < ramp_length {
+                (2.0 * acceleration * s_from_end).powf(0.5)
+            } else {
+                peak_velocity
+            }
+        })
+        .collect();
+
+    Ok(build_trajectory(&points, &arc_lengths, &speeds))
+}
+
+/// Inserts a sample at each of the increasing arc lengths of `boundaries` that does not already
+/// fall on a sample.
+fn insert_phase_boundaries<const D: usize>(
+    points: &[[f32; D]],
+    arc_lengths: &[f32],
+    boundaries: &[f32],
+) -> (Vec<[f32; D]>, Vec<f32>) {
+    let tolerance = 1E-4 * arc_lengths[arc_lengths.len() - 1];
+    let mut new_points = vec![points[0]];
+    let mut new_arc_lengths = vec![arc_lengths[0]];
+    for k in 1..points.len() {
+        let (s_from, s_to) = (arc_lengths[k - 1], arc_lengths[k]);
+        for &s in boundaries.iter() {
+            let is_inside = s_from + tolerance < s && s < s_to - tolerance;
+            if is_inside
+                && new_arc_lengths
+                    .last()
+                    .is_some_and(|&last| last + tolerance < s)
+            {
+                let ratio = (s - s_from) / (s_to - s_from);
+                let mut point = points[k - 1];
+                for (i, value) in point.iter_mut().enumerate() {
+                    *value += ratio * (points[k][i] - points[k - 1][i]);
+                }
+                new_points.push(point);
+                new_arc_lengths.push(s);
+            }
+        }
+        new_points.push(points[k]);
+        new_arc_lengths.push(s_to);
+    }
+    (new_points, new_arc_lengths)
+}
+
+/// Assigns the fastest rest-to-rest speed profile along densely sampled `points`.
+///
+/// The profile is found with a forward and a backward pass over the samples. Half of each
+/// acceleration limit is reserved for the centripetal term and half for the tangential term.
+/// Consecutive duplicate samples are dropped, and a single segment is split at its middle so that
+/// the speed can rise above zero between the ends. Fails unless every limit is positive and finite.
+pub fn parameterize_time_optimal<const D: usize>(
+    points: &[[f32; D]],
+    max_velocity: [f32; D],
+    max_acceleration: [f32; D],
+) -> Result<Trajectory<D>, Box<dyn Error>> {
+    check_limits(&max_velocity, &max_acceleration)?;
+
+    let mut points = points.to_vec();
+    points.dedup();
+    if points.len() == 2 {
+        let mut middle = points[0];
+        for (i, value) in middle.iter_mut().enumerate() {
+            *value = (*value + points[1][i]) / 2.0;
+        }
+        points.insert(1, middle);
+    }
+
+    let n = points.len();
+    if n < 2 {
+        let zeros = vec![0.0; n];
+        return Ok(build_trajectory(&points, &zeros, &zeros));
+    }
+
+    let arc_lengths = calc_arc_lengths(&points);
+    let tangents: Vec<[f32; D]> = (0..n)
+        .map(|k| calc_tangent(&points, &arc_lengths, k))
+        .collect();
+
+    // Speed limits from the velocity limits and the curvature of each axis
+    let mut speed_limits: Vec<f32> = vec![f32::MAX; n];
+    for k in 0..n {
+        for i in 0..D {
+            if tangents[k][i].abs() > 0.0 {
+                speed_limits[k] = speed_limits[k].min(max_velocity[i] / tangents[k][i].abs());
+            }
+        }
+        if 0 < k && k < n - 1 {
+            let ds = (arc_lengths[k + 1] - arc_lengths[k - 1]) / 2.0;
+            for i in 0..D {
+                let curvature = (tangents[k + 1][i] - tangents[k - 1][i]).abs() / (2.0 * ds);
+                if curvature > 0.0 {
+                    let limit = (max_acceleration[i] / 2.0 / curvature).powf(0.5);
+                    speed_limits[k] = speed_limits[k].min(limit);
+                }
+            }
+        }
+    }
+    speed_limits[0] = 0.0;
+    speed_limits[n - 1] = 0.0;
+
+    // Tangential acceleration limit of each segment
+    let acceleration_limits: Vec<f32> = (0..n - 1)
+        .map(|k| {
+            let mut limit = f32::MAX;
+            for i in 0..D {
+                let direction = ((tangents[k][i] + tangents[k + 1][i]) / 2.0).abs();
+                if direction > 0.0 {
+                    limit = limit.min(max_acceleration[i] / 2.0 / direction);
+                }
+            }
+            limit
+        })
+        .collect();
+
+    let mut speeds = speed_limits.clone();
+    for k in 0..n - 1 {
+        let ds = arc_lengths[k + 1] - arc_lengths[k];
+        let reachable = (speeds[k].powf(2.0) + 2.0 * acceleration_limits[k] * ds).powf(0.5);
+        speeds[k + 1] = speeds[k + 1].min(reachable);
+    }
+    for k in (0..n - 1).rev() {
+        let ds = arc_lengths[k + 1] - arc_lengths[k];
+        let reachable = (speeds[k + 1].powf(2.0) + 2.0 * acceleration_limits[k] * ds).powf(0.5);
+        speeds[k] = speeds[k].min(reachable);
+    }
+
+    Ok(build_trajectory(&points, &arc_lengths, &speeds))
+}
@@ -0,0 +1,131 @@
+use path_planning as pp;
+use pp::benchmark::{Benchmark, BenchmarkProblem};
+use pp::planner::{AbstractRRT, RRTStar, RRT};
+
+fn create_example_benchmark() -> Benchmark<2> {
+    let mut benchmark = Benchmark::new(3, 42);
+    benchmark.add_problem(BenchmarkProblem::new(
+        "example",
+        pp::env::create_example_2d_env(),
+        [1.0, 1.0],
+        [48.0, 25.0],
+    ));
+    benchmark.add_planner(
+        "rrt",
+        Box::new(|problem: &BenchmarkProblem<2>| {
+            let env = problem.env.clone();
+            let is_approved =
+                Box::new(move |position: &[f32; 2]| !env.is_inside_obstacle(position));
+            Box::new(RRT::new(
+                problem.start,
+                problem.goal,
+                problem.env.low,
+                problem.env.high,
+                is_approved,
+                0.2,
+                2.0,
+                2000,
+            )) as Box<dyn AbstractRRT<2>>
+        }),
+    );
+    benchmark.add_planner(
+        "rrtstar",
+        Box::new(|problem: &BenchmarkProblem<2>| {
+            let env = problem.env.clone();
+            let is_approved =
+                Box::new(move |position: &[f32; 2]| !env.is_inside_obstacle(position));
+            Box::new(RRTStar::new(
+                problem.start,
+                problem.goal,
+                problem.env.low,
+                problem.env.high,
+                is_approved,
+                0.2,
+                2.0,
+                500,
+            )) as Box<dyn AbstractRRT<2>>
+        }),
+    );
+    benchmark
+}
+
+#[test]
+fn test_benchmark() {
+    let benchmark = create_example_benchmark();
+    let results = benchmark.run();
+    assert_eq!(results.trials.len(), 6);
+
+    let summaries = results.summarize();
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[0].planner, "rrt");
+    assert_eq!(summaries[1].planner, "rrtstar");
+    for summary in summaries.iter() {
+        assert_eq!(summary.num_trials, 3);
+        assert_eq!(summary.success_rate, 1.0);
+        assert!(summary.mean_cost.unwrap() >= (47.0_f32.powf(2.0) + 24.0_f32.powf(2.0)).sqrt());
+    }
+
+    for trial in results.trials.iter() {
+        let cost_history = &trial.stats.cost_history;
+        assert!(!cost_history.is_empty());
+        assert_eq!(trial.stats.time_to_first_solution, Some(cost_history[0].0));
+        assert_eq!(trial.cost, Some(cost_history[cost_history.len() - 1].1));
+        assert!(cost_history.windows(2).all(|w| w[1].1 < w[0].1));
+    }
+
+    assert_eq!(results.to_csv().lines().count(), 7);
+    assert_eq!(results.summary_to_csv().lines().count(), 3);
+    assert!(results.cost_history_to_csv().lines().count() > 6);
+}
+
+#[test]
+fn test_benchmark_is_reproducible() {
+    let benchmark = create_example_benchmark();
+    let results = benchmark.run();
+    let other_results = benchmark.run();
+    for (trial, other_trial) in results.trials.iter().zip(other_results.trials.iter()) {
+        assert_eq!(trial.cost, other_trial.cost);
+        assert_eq!(trial.num_nodes, other_trial.num_nodes);
+    }
+}
+
+#[test]
+fn test_benchmark_time_budget() {
+    let mut benchmark = create_example_benchmark();
+    benchmark.max_time = Some(0.0);
+    let results = benchmark.run();
+    for trial in results.trials.iter() {
+        assert!(!trial.is_solved);
+        assert_eq!(trial.num_nodes, 1);
+        assert!(trial.cost.is_none());
+    }
+    assert!(results.summarize()[0].mean_cost.is_none());
+}
+
+#[test]
+fn test_csv_escaping() {
+    let mut results = create_example_benchmark().run();
+    for trial in results.trials.iter_mut() {
+        trial.planner = format!("{}, tuned", trial.planner);
+        trial.problem = String::from("\"narrow\" gap");
+    }
+
+    let csv = results.to_csv();
+    assert_eq!(csv.lines().count(), 7);
+    assert!(csv
+        .lines()
+        .skip(1)
+        .all(|line| line.starts_with("\"\"\"narrow\"\" gap\",\"rrt")));
+    assert!(csv.contains("\"\"\"narrow\"\" gap\",\"rrtstar, tuned\",0,"));
+
+    let summary_csv = results.summary_to_csv();
+    assert!(summary_csv.contains("\"\"\"narrow\"\" gap\",\"rrt, tuned\",3,"));
+    assert!(results
+        .cost_history_to_csv()
+        .lines()
+        .skip(1)
+        .all(|line| line.contains(" tuned\",")));
+
+    assert_eq!(pp::benchmark::escape_csv_field("rrt"), "rrt");
+    assert_eq!(pp::benchmark::escape_csv_field("a\nb"), "\"a\nb\"");
+}
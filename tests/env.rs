@@ -3,5 +3,134 @@ use path_planning as pp;
 #[test]
 fn test_env() {
     let env = pp::env::create_example_2d_env();
-    assert_eq!(env.obstacles.len(), 9);
+    assert_eq!(env.get_obstacles().len(), 9);
+}
+
+#[test]
+fn test_distance_to_nearest_obstacle() {
+    let env = pp::env::create_example_2d_env();
+
+    let (distance, index, gradient) = env.distance_to_nearest_obstacle(&[7.0, 17.0]).unwrap();
+    assert!((distance - 2.0).abs() < 1E-5);
+    assert_eq!(index, 4);
+    assert!(gradient[0].abs() < 1E-2);
+    assert!((gradient[1] - 1.0).abs() < 1E-2);
+
+    let (distance, index, _) = env.distance_to_nearest_obstacle(&[27.0, 13.0]).unwrap();
+    assert_eq!(distance, -1.0);
+    assert_eq!(index, 2);
+
+    let empty_env = pp::env::Env::new([0.0, 0.0], [1.0, 1.0], Vec::new());
+    assert!(empty_env
+        .distance_to_nearest_obstacle(&[0.5, 0.5])
+        .is_none());
+}
+
+#[test]
+fn test_is_intersecting_obstacle() {
+    let env = pp::env::create_example_2d_env();
+    assert!(env.is_intersecting_obstacle(&[20.0, 10.0], &[20.0, 16.0]));
+    assert!(!env.is_intersecting_obstacle(&[1.0, 1.0], &[1.0, 29.0]));
+}
+
+#[test]
+fn test_inflate() {
+    let env = pp::env::create_example_2d_env();
+    let inflated_env = env.inflate(1.0);
+
+    assert_eq!(
+        inflated_env.get_obstacles().len(),
+        env.get_obstacles().len()
+    );
+    assert!(!env.is_inside_obstacle(&[7.0, 15.5]));
+    assert!(inflated_env.is_inside_obstacle(&[7.0, 15.5]));
+    assert!(!inflated_env.is_inside_obstacle(&[7.0, 16.5]));
+}
+
+#[test]
+fn test_bvh() {
+    use pp::env::Obstacle;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut obstacles: Vec<Obstacle<2>> = Vec::new();
+    for _ in 0..500 {
+        let center = [rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0)];
+        let circle = |offset: [f32; 2], radius: f32| Obstacle::CircleObstacle {
+            center: [center[0] + offset[0], center[1] + offset[1]],
+            radius,
+        };
+        match rng.gen_range(0..4) {
+            0 => obstacles.push(Obstacle::RectObstacle {
+                center,
+                size: [rng.gen_range(0.1..3.0), rng.gen_range(0.1..3.0)],
+            }),
+            1 => obstacles.push(circle([0.0, 0.0], rng.gen_range(0.1..1.5))),
+            // The signed distances of these are only lower bounds of the true distances
+            2 => obstacles.push(Obstacle::IntersectionObstacle {
+                obstacles: vec![
+                    Obstacle::RectObstacle {
+                        center: [center[0] + 5.0, center[1]],
+                        size: [10.0, 1.0],
+                    },
+                    Obstacle::RectObstacle {
+                        center: [center[0], center[1] + 5.0],
+                        size: [1.0, 10.0],
+                    },
+                ],
+            }),
+            _ => obstacles.push(Obstacle::DifferenceObstacle {
+                base: Box::new(Obstacle::RectObstacle {
+                    center,
+                    size: [3.0, 2.0],
+                }),
+                subtracted: vec![circle([1.5, 1.0], 1.2)],
+            }),
+        }
+    }
+    obstacles.push(Obstacle::PolytopeObstacle {
+        normals: vec![[1.0, 0.0]],
+        offsets: vec![-5.0],
+    });
+
+    let env = pp::env::Env::new([0.0, 0.0], [100.0, 100.0], obstacles);
+    let mut bvh_env = env.clone();
+    bvh_env.build_bvh();
+
+    for _ in 0..1000 {
+        let from = [rng.gen_range(-10.0..110.0), rng.gen_range(-10.0..110.0)];
+        let to = [
+            from[0] + rng.gen_range(-5.0..5.0),
+            from[1] + rng.gen_range(-5.0..5.0),
+        ];
+        assert_eq!(
+            bvh_env.is_inside_obstacle(&from),
+            env.is_inside_obstacle(&from)
+        );
+        assert_eq!(
+            bvh_env.is_intersecting_obstacle(&from, &to),
+            env.is_intersecting_obstacle(&from, &to)
+        );
+
+        let (distance, index, _) = env.distance_to_nearest_obstacle(&from).unwrap();
+        let (bvh_distance, bvh_index, _) = bvh_env.distance_to_nearest_obstacle(&from).unwrap();
+        assert_eq!(bvh_distance, distance);
+        assert_eq!(bvh_index, index);
+    }
+
+    // Adding an obstacle updates the BVH
+    bvh_env.add_obstacle(Obstacle::CircleObstacle {
+        center: [50.0, 200.0],
+        radius: 1.0,
+    });
+    assert!(bvh_env.is_inside_obstacle(&[50.0, 200.0]));
+    assert!(bvh_env.is_intersecting_obstacle(&[40.0, 200.0], &[60.0, 200.0]));
+    assert_eq!(
+        bvh_env
+            .distance_to_nearest_obstacle(&[50.0, 203.0])
+            .unwrap()
+            .1,
+        bvh_env.get_obstacles().len() - 1
+    );
 }
@@ -0,0 +1,153 @@
+use path_planning::env::{CellState, Env, Footprint, Obstacle, OccupancyGrid};
+use path_planning::planner::{set_seed, wrap_angle, AbstractRRT, Node, RRT};
+use std::f32::consts::{FRAC_PI_2, PI};
+
+fn create_example_2d_env() -> Env<2> {
+    // Narrow vertical aisle of width 2 between two walls
+    let obstacles = vec![
+        Obstacle::RectObstacle {
+            center: [2.0, 5.0],
+            size: [4.0, 10.0],
+        },
+        Obstacle::RectObstacle {
+            center: [8.0, 5.0],
+            size: [4.0, 10.0],
+        },
+    ];
+    Env::new([0.0, 0.0], [10.0, 20.0], obstacles)
+}
+
+#[test]
+fn test_get_vertices_at() {
+    let footprint = Footprint::create_rect(4.0, 2.0);
+    let vertices = footprint.get_vertices_at(&[1.0, 1.0, FRAC_PI_2]);
+    assert!((vertices[0][0] - 2.0).abs() < 1E-5);
+    assert!((vertices[0][1] + 1.0).abs() < 1E-5);
+}
+
+#[test]
+fn test_is_in_collision() {
+    let env = create_example_2d_env();
+    let footprint = Footprint::create_rect(4.0, 1.0);
+
+    // The forklift fits in the aisle only when heading along it
+    assert!(!footprint.is_in_collision(&env, &[5.0, 5.0, FRAC_PI_2]));
+    assert!(footprint.is_in_collision(&env, &[5.0, 5.0, 0.0]));
+    assert!(!footprint.is_in_collision(&env, &[5.0, 15.0, 0.0]));
+
+    // Obstacle fully inside the footprint
+    let small_obstacle_env = Env::new(
+        [0.0, 0.0],
+        [10.0, 10.0],
+        vec![Obstacle::CircleObstacle {
+            center: [5.5, 5.0],
+            radius: 0.2,
+        }],
+    );
+    assert!(footprint.is_in_collision(&small_obstacle_env, &[5.0, 5.0, 0.0]));
+    assert!(!footprint.is_in_collision(&small_obstacle_env, &[5.0, 7.0, 0.0]));
+
+    // The same results with a BVH
+    let mut bvh_env = create_example_2d_env();
+    bvh_env.add_obstacle(Obstacle::CircleObstacle {
+        center: [5.5, 15.0],
+        radius: 0.2,
+    });
+    bvh_env.build_bvh();
+    assert!(!footprint.is_in_collision(&bvh_env, &[5.0, 5.0, FRAC_PI_2]));
+    assert!(footprint.is_in_collision(&bvh_env, &[5.0, 5.0, 0.0]));
+    assert!(footprint.is_in_collision(&bvh_env, &[5.0, 15.0, 0.0]));
+    assert!(!footprint.is_in_collision(&bvh_env, &[5.0, 17.0, 0.0]));
+}
+
+#[test]
+#[should_panic]
+fn test_non_convex_footprint() {
+    Footprint::new(vec![
+        [0.0, 0.0],
+        [2.0, 0.0],
+        [1.0, 0.5],
+        [2.0, 1.0],
+        [0.0, 1.0],
+    ]);
+}
+
+#[test]
+fn test_is_in_collision_with_grid() {
+    let mut grid = OccupancyGrid::new([0.0, 0.0], 0.5, [20, 20]);
+    grid.set_cell(&[10, 10], CellState::Occupied);
+    let footprint = Footprint::create_rect(4.0, 2.0);
+
+    assert!(footprint.is_in_collision_with_grid(&grid, &[5.2, 5.2, 0.0]));
+    assert!(!footprint.is_in_collision_with_grid(&grid, &[5.2, 8.0, 0.0]));
+    assert!(footprint.is_in_collision_with_grid(&grid, &[5.2, 8.0, FRAC_PI_2]));
+}
+
+#[test]
+fn test_plan() {
+    let env = create_example_2d_env();
+    let footprint = Footprint::create_rect(3.0, 1.0);
+
+    let start: [f32; 3] = [5.0, 1.5, FRAC_PI_2];
+    let goal: [f32; 3] = [5.0, 15.0, 0.0];
+    let low: [f32; 3] = [0.0, 0.0, -PI];
+    let high: [f32; 3] = [10.0, 20.0, PI];
+    let _env = env.clone();
+    let _footprint = footprint.clone();
+    let is_approved = Box::new(move |pose: &[f32; 3]| !_footprint.is_in_collision(&_env, pose));
+
+    let mut rrt = RRT::new(start, goal, low, high, is_approved, 0.2, 0.5, 20000);
+    let path = rrt.plan();
+
+    assert!(!path.is_empty());
+    for pose in path.iter() {
+        assert!(!footprint.is_in_collision(&env, pose));
+    }
+}
+
+#[test]
+fn test_plan_across_pi() {
+    // Horizontal aisle of width 2 in which a footprint of length 3 cannot turn around
+    let obstacles = vec![
+        Obstacle::RectObstacle {
+            center: [10.0, 2.0],
+            size: [20.0, 4.0],
+        },
+        Obstacle::RectObstacle {
+            center: [10.0, 8.0],
+            size: [20.0, 4.0],
+        },
+    ];
+    let env = Env::new([0.0, 0.0], [20.0, 10.0], obstacles);
+    let footprint = Footprint::create_rect(3.0, 1.0);
+    assert!(footprint.is_in_collision(&env, &[10.0, 5.0, FRAC_PI_2]));
+
+    // Both headings point along the aisle, on either side of theta = pi
+    let start: [f32; 3] = [2.0, 5.0, 3.1];
+    let goal: [f32; 3] = [18.0, 5.0, -3.1];
+    let low: [f32; 3] = [0.0, 0.0, -PI];
+    let high: [f32; 3] = [20.0, 10.0, PI];
+    let _env = env.clone();
+    let _footprint = footprint.clone();
+    let is_approved = Box::new(move |pose: &[f32; 3]| !_footprint.is_in_collision(&_env, pose));
+
+    set_seed(0);
+    let mut rrt = RRT::new(start, goal, low, high, is_approved, 0.2, 0.5, 20000);
+    rrt.angular_axes = [false, false, true];
+    rrt.edge_resolution = Some(0.1);
+    assert!((rrt.calc_distance(&start, &goal) - 16.0).abs() < 1E-3);
+
+    let extended_node =
+        rrt.get_extended_node(&Node::new([0.0, 0.0, 3.0]), &Node::new([0.0, 0.0, -3.0]));
+    assert!((extended_node.position[2] - (3.5 - 2.0 * PI)).abs() < 1E-5);
+
+    let path = rrt.plan();
+    assert!(!path.is_empty());
+    for edge in path.windows(2) {
+        assert!(wrap_angle(edge[1][2] - edge[0][2]).abs() <= 0.5 + 1E-5);
+        for step in 0..=10 {
+            let pose = rrt.interpolate(&edge[0], &edge[1], step as f32 / 10.0);
+            assert!(!footprint.is_in_collision(&env, &pose));
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use path_planning::planner::InformedRRTStar;
+
+#[test]
+fn test_plan() {
+    let low: [f32; 2] = [0.0, 0.0];
+    let high: [f32; 2] = [50.0, 30.0];
+    let start: [f32; 2] = [1.0, 1.0];
+    let goal: [f32; 2] = [48.0, 25.0];
+    let is_approved = Box::new(|_position: &[f32; 2]| true);
+    let goal_sample_rate = 0.2;
+    let step_size = 2.0;
+    let max_iter = 2000;
+
+    let mut rrt = InformedRRTStar::new(
+        start,
+        goal,
+        low,
+        high,
+        is_approved,
+        goal_sample_rate,
+        step_size,
+        max_iter,
+    );
+    let path = rrt.plan();
+
+    assert!(path.len() > 0);
+    assert_eq!(path[0], start);
+    assert_eq!(path[path.len() - 1], goal);
+    for position in rrt.nodes.iter().map(|node| node.position) {
+        for i in 0..2 {
+            assert!(low[i] <= position[i] && position[i] <= high[i]);
+        }
+    }
+}
+
+#[test]
+fn test_plan_3d() {
+    let low: [f32; 3] = [0.0, 0.0, 0.0];
+    let high: [f32; 3] = [10.0, 10.0, 10.0];
+    let start: [f32; 3] = [1.0, 1.0, 1.0];
+    let goal: [f32; 3] = [9.0, 9.0, 9.0];
+    let is_approved = Box::new(|_position: &[f32; 3]| true);
+
+    let mut rrt = InformedRRTStar::new(start, goal, low, high, is_approved, 0.2, 1.0, 1000);
+    let path = rrt.plan();
+
+    assert!(path.len() > 0);
+    assert_eq!(path[0], start);
+    assert_eq!(path[path.len() - 1], goal);
+    for position in rrt.nodes.iter().map(|node| node.position) {
+        for i in 0..3 {
+            assert!(low[i] <= position[i] && position[i] <= high[i]);
+        }
+    }
+}
+
+#[test]
+fn test_plan_1d() {
+    let low: [f32; 1] = [0.0];
+    let high: [f32; 1] = [10.0];
+    let start: [f32; 1] = [1.0];
+    let goal: [f32; 1] = [9.0];
+    let is_approved = Box::new(|_position: &[f32; 1]| true);
+
+    let mut rrt = InformedRRTStar::new(start, goal, low, high, is_approved, 0.2, 1.0, 200);
+    let path = rrt.plan();
+
+    assert!(path.len() > 0);
+    assert_eq!(path[0], start);
+    assert_eq!(path[path.len() - 1], goal);
+}
@@ -0,0 +1,45 @@
+use path_planning::env::{Env, Obstacle};
+use path_planning::path::*;
+
+fn create_example_2d_env() -> Env<2> {
+    let obstacles = vec![
+        Obstacle::RectObstacle {
+            center: [5.0, 3.0],
+            size: [2.0, 2.0],
+        },
+        Obstacle::CircleObstacle {
+            center: [5.0, -4.0],
+            radius: 1.0,
+        },
+    ];
+    Env::new([0.0, -10.0], [10.0, 10.0], obstacles)
+}
+
+#[test]
+fn test_clearance() {
+    let env = create_example_2d_env();
+    let path = Path::new(vec![[0.0, 0.0], [10.0, 0.0]]);
+
+    assert!((calc_min_clearance(&path, &env, 0.1) - 2.0).abs() < 1E-4);
+    let clearances = calc_clearances(&path, &env, 0.1);
+    assert!((clearances[0] - 4.0_f32.hypot(2.0)).abs() < 1E-4);
+
+    let average_clearance = calc_average_clearance(&path, &env, 0.1);
+    assert!(2.0 < average_clearance && average_clearance < 4.0_f32.hypot(2.0));
+
+    let path = Path::new(vec![[0.0, 3.0], [10.0, 3.0]]);
+    assert_eq!(calc_min_clearance(&path, &env, 0.1), 0.0);
+}
+
+#[test]
+fn test_path_metrics() {
+    let env = create_example_2d_env();
+    let path = Path::new(vec![[0.0, 0.0], [3.0, 0.0], [3.0, -3.0]]);
+    let metrics = PathMetrics::new(&path, &env, 0.1);
+
+    assert_eq!(metrics.length, 6.0);
+    assert_eq!(metrics.num_waypoints, 3);
+    assert!((metrics.smoothness - std::f32::consts::FRAC_PI_2).abs() < 1E-5);
+    assert!((metrics.max_curvature - std::f32::consts::FRAC_PI_2 / 3.0).abs() < 1E-5);
+    assert!((metrics.min_clearance - (5.0_f32.sqrt() - 1.0)).abs() < 1E-4);
+}
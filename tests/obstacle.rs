@@ -1,4 +1,4 @@
-use path_planning::env::Obstacle;
+use path_planning::env::{create_2d_rotation, create_identity_rotation, Obstacle};
 
 #[test]
 fn test_rect_obstacle() {
@@ -25,3 +25,313 @@ fn test_circle_obstacle() {
     let position: [f32; 2] = [1.8, 1.8];
     assert!(!circle_obstacle.is_inside(&position));
 }
+
+#[test]
+fn test_signed_distance() {
+    let rect_obstacle = Obstacle::RectObstacle {
+        center: [0.0, 0.0],
+        size: [2.0, 2.0],
+    };
+    assert_eq!(rect_obstacle.signed_distance(&[3.0, 0.0]), 2.0);
+    assert_eq!(rect_obstacle.signed_distance(&[4.0, 5.0]), 5.0);
+    assert_eq!(rect_obstacle.signed_distance(&[0.5, 0.0]), -0.5);
+
+    let circle_obstacle = Obstacle::CircleObstacle {
+        center: [0.0, 0.0],
+        radius: 2.0,
+    };
+    assert_eq!(circle_obstacle.signed_distance(&[3.0, 4.0]), 3.0);
+    assert_eq!(circle_obstacle.signed_distance(&[0.0, 0.5]), -1.5);
+
+    let gradient = circle_obstacle.calc_gradient(&[3.0, 4.0]);
+    assert!((gradient[0] - 0.6).abs() < 1E-2);
+    assert!((gradient[1] - 0.8).abs() < 1E-2);
+}
+
+#[test]
+fn test_calc_gradient() {
+    // Coordinates at the scale of the example environment
+    let rect_obstacle = Obstacle::RectObstacle {
+        center: [50.0, 50.0],
+        size: [10.0, 10.0],
+    };
+    assert_eq!(rect_obstacle.calc_gradient(&[57.0, 51.0]), [1.0, 0.0]);
+    assert_eq!(rect_obstacle.calc_gradient(&[50.5, 46.0]), [0.0, -1.0]);
+
+    let rotated_rect_obstacle = Obstacle::OrientedRectObstacle {
+        center: [50.0, 50.0],
+        size: [10.0, 10.0],
+        rotation: create_2d_rotation(std::f32::consts::FRAC_PI_2),
+    };
+    let gradient = rotated_rect_obstacle.calc_gradient(&[57.0, 51.0]);
+    assert!((gradient[0] - 1.0).abs() < 1E-5 && gradient[1].abs() < 1E-5);
+
+    let polygon_obstacle = Obstacle::PolygonObstacle {
+        vertices: vec![[45.0, 45.0], [55.0, 45.0], [55.0, 55.0], [45.0, 55.0]],
+    };
+    for position in [[56.0, 50.3], [50.3, 54.9]] {
+        let gradient = polygon_obstacle.calc_gradient(&position);
+        let expected = rect_obstacle.calc_gradient(&position);
+        assert!((gradient[0] - expected[0]).abs() < 1E-3);
+        assert!((gradient[1] - expected[1]).abs() < 1E-3);
+    }
+
+    let difference_obstacle = Obstacle::DifferenceObstacle {
+        base: Box::new(rect_obstacle),
+        subtracted: vec![Obstacle::CircleObstacle {
+            center: [50.0, 50.0],
+            radius: 2.0,
+        }],
+    };
+    assert_eq!(
+        difference_obstacle.calc_gradient(&[51.0, 50.0]),
+        [-1.0, 0.0]
+    );
+}
+
+#[test]
+fn test_polygon_obstacle() {
+    // L-shaped polygon
+    let vertices: Vec<[f32; 2]> = vec![
+        [0.0, 0.0],
+        [4.0, 0.0],
+        [4.0, 1.0],
+        [1.0, 1.0],
+        [1.0, 4.0],
+        [0.0, 4.0],
+    ];
+    let polygon_obstacle = Obstacle::PolygonObstacle { vertices };
+
+    assert!(polygon_obstacle.is_inside(&[0.5, 3.0]));
+    assert!(polygon_obstacle.is_inside(&[3.0, 0.5]));
+    assert!(!polygon_obstacle.is_inside(&[2.0, 2.0]));
+
+    assert!((polygon_obstacle.signed_distance(&[2.0, 2.0]) - 1.0).abs() < 1E-5);
+    assert!((polygon_obstacle.signed_distance(&[0.5, 3.0]) + 0.5).abs() < 1E-5);
+
+    assert!(polygon_obstacle.is_intersecting_segment(&[2.0, 2.0], &[2.0, -1.0]));
+    assert!(!polygon_obstacle.is_intersecting_segment(&[2.0, 2.0], &[5.0, 5.0]));
+}
+
+#[test]
+fn test_polytope_obstacle() {
+    // Triangle x >= 0, y >= 0, x + y <= 2
+    let normals: Vec<[f32; 2]> = vec![[-1.0, 0.0], [0.0, -1.0], [1.0, 1.0]];
+    let offsets: Vec<f32> = vec![0.0, 0.0, 2.0];
+    let polytope_obstacle = Obstacle::PolytopeObstacle { normals, offsets };
+
+    assert!(polytope_obstacle.is_inside(&[0.5, 0.5]));
+    assert!(!polytope_obstacle.is_inside(&[1.5, 1.5]));
+
+    assert!((polytope_obstacle.signed_distance(&[2.0, 2.0]) - 2.0_f32.sqrt()).abs() < 1E-4);
+    assert!((polytope_obstacle.signed_distance(&[-3.0, -4.0]) - 5.0).abs() < 1E-4);
+    assert!((polytope_obstacle.signed_distance(&[0.5, 0.5]) + 0.5).abs() < 1E-5);
+
+    assert!(polytope_obstacle.is_intersecting_segment(&[-1.0, 0.5], &[3.0, 0.5]));
+    assert!(!polytope_obstacle.is_intersecting_segment(&[-1.0, 3.0], &[3.0, 3.0]));
+}
+
+#[test]
+fn test_is_intersecting_segment() {
+    let rect_obstacle = Obstacle::RectObstacle {
+        center: [0.0, 0.0],
+        size: [2.0, 2.0],
+    };
+    assert!(rect_obstacle.is_intersecting_segment(&[-2.0, 0.0], &[2.0, 0.0]));
+    assert!(!rect_obstacle.is_intersecting_segment(&[-2.0, 2.0], &[2.0, 2.0]));
+
+    let circle_obstacle = Obstacle::CircleObstacle {
+        center: [0.0, 0.0],
+        radius: 1.0,
+    };
+    assert!(circle_obstacle.is_intersecting_segment(&[-2.0, 0.5], &[2.0, 0.5]));
+    assert!(!circle_obstacle.is_intersecting_segment(&[-2.0, 1.5], &[2.0, 1.5]));
+}
+
+#[test]
+fn test_oriented_rect_obstacle() {
+    let rotation = create_2d_rotation(std::f32::consts::FRAC_PI_4);
+    let oriented_rect_obstacle = Obstacle::OrientedRectObstacle {
+        center: [0.0, 0.0],
+        size: [4.0, 1.0],
+        rotation,
+    };
+
+    assert!(oriented_rect_obstacle.is_inside(&[1.0, 1.0]));
+    assert!(!oriented_rect_obstacle.is_inside(&[1.0, -1.0]));
+    assert!(
+        (oriented_rect_obstacle.signed_distance(&[1.0, -1.0]) - (2.0_f32.sqrt() - 0.5)).abs()
+            < 1E-5
+    );
+
+    assert!(oriented_rect_obstacle.is_intersecting_segment(&[-1.0, 2.0], &[2.0, -1.0]));
+    assert!(!oriented_rect_obstacle.is_intersecting_segment(&[2.0, 0.0], &[0.0, -2.0]));
+}
+
+#[test]
+fn test_ellipsoid_obstacle() {
+    let ellipsoid_obstacle = Obstacle::EllipsoidObstacle {
+        center: [1.0, 1.0, 1.0],
+        radiuses: [3.0, 2.0, 1.0],
+        rotation: create_identity_rotation(),
+    };
+
+    assert!(ellipsoid_obstacle.is_inside(&[3.5, 1.0, 1.0]));
+    assert!(!ellipsoid_obstacle.is_inside(&[1.0, 1.0, 2.5]));
+    assert!((ellipsoid_obstacle.signed_distance(&[6.0, 1.0, 1.0]) - 2.0).abs() < 1E-4);
+    assert!((ellipsoid_obstacle.signed_distance(&[1.0, 1.0, 1.0]) + 1.0).abs() < 1E-4);
+    assert!((ellipsoid_obstacle.signed_distance(&[1.0, 1.0, 1.5]) + 0.5).abs() < 1E-4);
+
+    assert!(ellipsoid_obstacle.is_intersecting_segment(&[1.0, -5.0, 1.0], &[1.0, 5.0, 1.0]));
+    assert!(!ellipsoid_obstacle.is_intersecting_segment(&[1.0, -5.0, 2.5], &[1.0, 5.0, 2.5]));
+}
+
+#[test]
+fn test_capsule_obstacle() {
+    let capsule_obstacle = Obstacle::CapsuleObstacle {
+        start: [0.0, 0.0],
+        end: [4.0, 0.0],
+        radius: 1.0,
+    };
+
+    assert!(capsule_obstacle.is_inside(&[2.0, 0.5]));
+    assert!(capsule_obstacle.is_inside(&[4.5, 0.5]));
+    assert!(!capsule_obstacle.is_inside(&[2.0, 1.5]));
+    assert_eq!(capsule_obstacle.signed_distance(&[7.0, 4.0]), 4.0);
+
+    assert!(capsule_obstacle.is_intersecting_segment(&[2.0, 3.0], &[2.0, -3.0]));
+    assert!(capsule_obstacle.is_intersecting_segment(&[-1.0, 2.0], &[6.0, 0.5]));
+    assert!(!capsule_obstacle.is_intersecting_segment(&[-2.0, 2.0], &[6.0, 2.0]));
+}
+
+fn create_example_room() -> Obstacle<2> {
+    // Walls of a 10 x 10 room with a doorway on the right
+    Obstacle::DifferenceObstacle {
+        base: Box::new(Obstacle::RectObstacle {
+            center: [0.0, 0.0],
+            size: [10.0, 10.0],
+        }),
+        subtracted: vec![
+            Obstacle::RectObstacle {
+                center: [0.0, 0.0],
+                size: [9.0, 9.0],
+            },
+            Obstacle::RectObstacle {
+                center: [5.0, 0.0],
+                size: [2.0, 2.0],
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_union_obstacle() {
+    // L-shaped wall
+    let union_obstacle = Obstacle::UnionObstacle {
+        obstacles: vec![
+            Obstacle::RectObstacle {
+                center: [2.0, 0.0],
+                size: [4.0, 1.0],
+            },
+            Obstacle::RectObstacle {
+                center: [0.0, 2.0],
+                size: [1.0, 4.0],
+            },
+        ],
+    };
+
+    assert!(union_obstacle.is_inside(&[3.0, 0.0]));
+    assert!(union_obstacle.is_inside(&[0.0, 3.0]));
+    assert!(!union_obstacle.is_inside(&[2.0, 2.0]));
+    assert_eq!(union_obstacle.signed_distance(&[2.0, 2.0]), 1.5);
+    assert!(union_obstacle.is_intersecting_segment(&[2.0, 2.0], &[-2.0, 2.0]));
+    assert!(!union_obstacle.is_intersecting_segment(&[2.0, 2.0], &[4.0, 4.0]));
+}
+
+#[test]
+fn test_intersection_obstacle() {
+    let intersection_obstacle = Obstacle::IntersectionObstacle {
+        obstacles: vec![
+            Obstacle::CircleObstacle {
+                center: [0.0, 0.0],
+                radius: 2.0,
+            },
+            Obstacle::RectObstacle {
+                center: [2.0, 0.0],
+                size: [4.0, 4.0],
+            },
+        ],
+    };
+
+    assert!(intersection_obstacle.is_inside(&[1.0, 0.0]));
+    assert!(!intersection_obstacle.is_inside(&[-1.0, 0.0]));
+    assert!(!intersection_obstacle.is_inside(&[3.0, 0.0]));
+    assert_eq!(intersection_obstacle.signed_distance(&[-1.0, 0.0]), 1.0);
+    assert!(intersection_obstacle.is_intersecting_segment(&[-1.0, 0.0], &[3.0, 0.0]));
+    assert!(!intersection_obstacle.is_intersecting_segment(&[-1.0, -3.0], &[-1.0, 3.0]));
+}
+
+#[test]
+fn test_difference_obstacle() {
+    let room = create_example_room();
+
+    assert!(room.is_inside(&[-4.8, 0.0]));
+    assert!(!room.is_inside(&[0.0, 0.0]));
+    assert!(!room.is_inside(&[4.8, 0.0]));
+    assert!((room.signed_distance(&[0.0, 0.0]) - 4.5).abs() < 1E-5);
+    assert!(room.signed_distance(&[0.0, -4.75]) < 0.0);
+
+    // Leaving through the doorway does not touch the walls, but leaving through a wall does
+    assert!(!room.is_intersecting_segment(&[0.0, 0.0], &[8.0, 0.0]));
+    assert!(room.is_intersecting_segment(&[0.0, 0.0], &[0.0, 8.0]));
+    assert!(room.is_intersecting_segment(&[0.0, 0.0], &[8.0, 4.0]));
+}
+
+#[test]
+fn test_inflate() {
+    let circle_obstacle = Obstacle::CircleObstacle {
+        center: [0.0, 0.0],
+        radius: 1.0,
+    };
+    match circle_obstacle.inflate(0.5) {
+        Obstacle::CircleObstacle { radius, .. } => assert_eq!(radius, 1.5),
+        _ => panic!("inflated circle must be a circle"),
+    }
+
+    // Rounded box
+    let rect_obstacle = Obstacle::RectObstacle {
+        center: [0.0, 0.0],
+        size: [2.0, 2.0],
+    };
+    let inflated_rect_obstacle = rect_obstacle.inflate(1.0);
+    assert!(inflated_rect_obstacle.is_inside(&[1.9, 0.0]));
+    assert!(inflated_rect_obstacle.is_inside(&[1.7, 1.7]));
+    assert!(!inflated_rect_obstacle.is_inside(&[1.8, 1.8]));
+    assert!((inflated_rect_obstacle.signed_distance(&[4.0, 0.0]) - 2.0).abs() < 1E-5);
+    assert!(inflated_rect_obstacle.is_intersecting_segment(&[-3.0, 1.5], &[3.0, 1.5]));
+    assert!(!inflated_rect_obstacle.is_intersecting_segment(&[-3.0, 2.5], &[3.0, 2.5]));
+}
+
+#[test]
+fn test_calc_aabb() {
+    let circle = Obstacle::CircleObstacle {
+        center: [1.0, 2.0],
+        radius: 0.5,
+    };
+    assert_eq!(circle.calc_aabb(), ([0.5, 1.5], [1.5, 2.5]));
+
+    let oriented_rect = Obstacle::OrientedRectObstacle {
+        center: [0.0, 0.0],
+        size: [2.0, 2.0],
+        rotation: create_2d_rotation(std::f32::consts::FRAC_PI_4),
+    };
+    let (low, high) = oriented_rect.calc_aabb();
+    assert!((high[0] - 2.0_f32.sqrt()).abs() < 1E-5);
+    assert!((low[1] + 2.0_f32.sqrt()).abs() < 1E-5);
+
+    let polytope = Obstacle::PolytopeObstacle {
+        normals: vec![[1.0, 0.0]],
+        offsets: vec![1.0],
+    };
+    assert_eq!(polytope.calc_aabb(), ([f32::MIN; 2], [f32::MAX; 2]));
+}
@@ -0,0 +1,66 @@
+use path_planning::env::{AbstractEnv, CellState, OccupancyGrid};
+
+fn create_example_2d_grid() -> OccupancyGrid<2> {
+    // 10 x 10 grid of 0.5 m cells with a wall at x = 2.0 ~ 2.5 and an unknown cell
+    let mut grid = OccupancyGrid::new([-1.0, -1.0], 0.5, [10, 10]);
+    for j in 0..8 {
+        grid.set_cell(&[6, j], CellState::Occupied);
+    }
+    grid.set_cell(&[0, 9], CellState::Unknown);
+    grid
+}
+
+#[test]
+fn test_init() {
+    let grid = create_example_2d_grid();
+    assert_eq!(grid.get_low(), &[-1.0, -1.0]);
+    assert_eq!(grid.get_high(), &[4.0, 4.0]);
+    assert_eq!(grid.get_cells().len(), 100);
+    assert_eq!(grid.get_cell_index(&[2.2, -0.9]), Some([6, 0]));
+    assert_eq!(grid.get_cell_index(&[4.1, 0.0]), None);
+    assert_eq!(grid.get_cell_center(&[6, 0]), [2.25, -0.75]);
+}
+
+#[test]
+fn test_is_inside_obstacle() {
+    let mut grid = create_example_2d_grid();
+    assert!(grid.is_inside_obstacle(&[2.2, 0.0]));
+    assert!(!grid.is_inside_obstacle(&[1.0, 0.0]));
+    assert!(grid.is_inside_obstacle(&[-0.8, 3.8]));
+    assert!(grid.is_inside_obstacle(&[5.0, 0.0]));
+
+    grid.is_unknown_occupied = false;
+    assert!(!grid.is_inside_obstacle(&[-0.8, 3.8]));
+    assert!(!grid.is_inside_obstacle(&[5.0, 0.0]));
+}
+
+#[test]
+fn test_is_intersecting_obstacle() {
+    let grid = create_example_2d_grid();
+    assert!(grid.is_intersecting_obstacle(&[0.0, 0.0], &[3.0, 0.0]));
+    assert!(grid.is_intersecting_obstacle(&[0.0, 0.0], &[3.0, 2.0]));
+    assert!(!grid.is_intersecting_obstacle(&[0.0, 0.0], &[1.9, 1.9]));
+    assert!(!grid.is_intersecting_obstacle(&[0.0, 3.2], &[3.0, 3.2]));
+    assert!(!grid.is_intersecting_obstacle(&[3.0, 3.0], &[3.5, -0.5]));
+}
+
+#[test]
+fn test_inflate() {
+    let mut grid = OccupancyGrid::new([0.0, 0.0], 1.0, [7, 7]);
+    grid.set_cell(&[3, 3], CellState::Occupied);
+    let inflated_grid = grid.inflate(1.0);
+
+    assert_eq!(inflated_grid.get_cell(&[3, 3]), CellState::Occupied);
+    assert_eq!(inflated_grid.get_cell(&[4, 4]), CellState::Occupied);
+    assert_eq!(inflated_grid.get_cell(&[5, 3]), CellState::Occupied);
+    assert_eq!(inflated_grid.get_cell(&[5, 5]), CellState::Free);
+    assert_eq!(inflated_grid.get_cell(&[6, 3]), CellState::Free);
+    assert_eq!(
+        inflated_grid
+            .get_cells()
+            .iter()
+            .filter(|&&state| state == CellState::Occupied)
+            .count(),
+        21
+    );
+}
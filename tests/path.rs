@@ -0,0 +1,87 @@
+use path_planning::path::Path;
+
+fn create_example_2d_path() -> Path<2> {
+    Path::new(vec![[0.0, 0.0], [3.0, 0.0], [3.0, 4.0]])
+}
+
+#[test]
+fn test_length() {
+    let path = create_example_2d_path();
+    assert_eq!(path.get_length(), 7.0);
+    assert_eq!(path.get_arc_lengths(), vec![0.0, 3.0, 7.0]);
+}
+
+#[test]
+fn test_interpolate() {
+    let path = create_example_2d_path();
+    assert_eq!(path.interpolate(-1.0), [0.0, 0.0]);
+    assert_eq!(path.interpolate(1.5), [1.5, 0.0]);
+    assert_eq!(path.interpolate(5.0), [3.0, 2.0]);
+    assert_eq!(path.interpolate(10.0), [3.0, 4.0]);
+}
+
+#[test]
+fn test_resample() {
+    let path = create_example_2d_path();
+
+    let resampled_path = path.resample_by_spacing(2.0);
+    assert_eq!(
+        resampled_path.waypoints,
+        vec![[0.0, 0.0], [2.0, 0.0], [3.0, 1.0], [3.0, 3.0], [3.0, 4.0]]
+    );
+
+    let resampled_path = path.resample_by_count(8);
+    assert_eq!(resampled_path.len(), 8);
+    assert_eq!(resampled_path.waypoints[0], [0.0, 0.0]);
+    assert_eq!(resampled_path.waypoints[3], [3.0, 0.0]);
+    assert_eq!(resampled_path.waypoints[7], [3.0, 4.0]);
+
+    let densified_path = path.densify(1.0);
+    assert_eq!(densified_path.len(), 8);
+    assert!((densified_path.get_length() - path.get_length()).abs() < 1E-5);
+}
+
+#[test]
+fn test_resample_short_paths() {
+    let empty_path: Path<2> = Path::new(vec![]);
+    assert!(empty_path.resample_by_spacing(1.0).waypoints.is_empty());
+
+    let single_point_path = Path::new(vec![[1.0, 2.0]]);
+    assert_eq!(
+        single_point_path.resample_by_spacing(1.0).waypoints,
+        vec![[1.0, 2.0]]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_resample_by_zero_spacing() {
+    create_example_2d_path().resample_by_spacing(0.0);
+}
+
+#[test]
+#[should_panic]
+fn test_densify_by_nan_spacing() {
+    create_example_2d_path().densify(f32::NAN);
+}
+
+#[test]
+fn test_curvatures() {
+    let path = create_example_2d_path();
+    let curvatures = path.calc_curvatures();
+    assert_eq!(curvatures[0], 0.0);
+    assert!((curvatures[1] - std::f32::consts::FRAC_PI_2 / 3.5).abs() < 1E-5);
+    assert_eq!(curvatures[2], 0.0);
+}
+
+#[test]
+fn test_closest_point() {
+    let path = create_example_2d_path();
+    let (point, s) = path.calc_closest_point(&[4.0, 2.0]).unwrap();
+    assert_eq!(point, [3.0, 2.0]);
+    assert_eq!(s, 5.0);
+
+    assert!(Path::<2>::new(Vec::new())
+        .calc_closest_point(&[0.0, 0.0])
+        .is_none());
+}
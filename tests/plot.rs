@@ -0,0 +1,398 @@
+use path_planning as pp;
+use pp::plot::{AnimationMode, AnimationOptions, EnvFigure, Figure, PathFigure, PlotBuilder};
+
+#[test]
+fn test_to_rgb() {
+    let env = pp::env::create_example_2d_env();
+    let path = vec![[1.0, 1.0], [25.0, 15.0], [48.0, 25.0]];
+
+    let size = (200, 120);
+    let pixels = PathFigure {
+        env: &env,
+        path: &path,
+    }
+    .to_rgb(size)
+    .unwrap();
+    assert_eq!(pixels.len(), 200 * 120 * 3);
+
+    // The margin stays white while the obstacles and the path are drawn in colour
+    assert_eq!(&pixels[0..3], &[255, 255, 255]);
+    assert!(pixels
+        .chunks(3)
+        .any(|pixel| pixel != [255, 255, 255] && pixel != [0, 0, 0]));
+}
+
+#[test]
+fn test_to_svg_string() {
+    let env = pp::env::create_example_2d_env();
+    let svg = EnvFigure { env: &env }.to_svg_string((400, 240)).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("width=\"400\""));
+    assert!(svg.contains("height=\"240\""));
+}
+
+#[test]
+fn test_csg_obstacle_svg_size() {
+    use pp::env::Obstacle::*;
+
+    let obstacles = vec![DifferenceObstacle {
+        base: Box::new(RectObstacle {
+            center: [50.0, 50.0],
+            size: [60.0, 60.0],
+        }),
+        subtracted: vec![CircleObstacle {
+            center: [50.0, 50.0],
+            radius: 20.0,
+        }],
+    }];
+    let env = pp::env::Env::new([0.0, 0.0], [100.0, 100.0], obstacles);
+
+    // Each row of the raster is drawn as at most two rectangles, one on each side of the hole
+    let svg = EnvFigure { env: &env }.to_svg_string((400, 300)).unwrap();
+    let num_rects = svg.matches("<rect").count();
+    assert!(num_rects > 200);
+    assert!(num_rects < 1000);
+}
+
+#[test]
+fn test_save() {
+    let env = pp::env::create_example_2d_env();
+    let dir = std::env::temp_dir();
+
+    let svg_path = dir.join("path_planning_test_save.svg");
+    EnvFigure { env: &env }.save(&svg_path, (400, 240)).unwrap();
+    assert!(std::fs::read_to_string(&svg_path)
+        .unwrap()
+        .starts_with("<svg"));
+
+    let png_path = dir.join("path_planning_test_save.png");
+    EnvFigure { env: &env }.save(&png_path, (400, 240)).unwrap();
+    let image = image::open(&png_path).unwrap();
+    assert_eq!((image.width(), image.height()), (400, 240));
+
+    std::fs::remove_file(svg_path).unwrap();
+    std::fs::remove_file(png_path).unwrap();
+}
+
+#[test]
+fn test_plot_builder() {
+    let env = pp::env::create_example_2d_env();
+    let _env = env.clone();
+    let is_approved = Box::new(move |position: &[f32; 2]| !_env.is_inside_obstacle(position));
+
+    let (start, goal) = ([1.0, 1.0], [48.0, 25.0]);
+    let mut rrtstar =
+        pp::planner::RRTStar::new(start, goal, env.low, env.high, is_approved, 0.2, 2.0, 1000);
+    let path = rrtstar.plan();
+    let straight_path = vec![start, goal];
+
+    let plot = PlotBuilder::new(&env)
+        .tree(&rrtstar.nodes)
+        .path(&path, "rrt*")
+        .path(&straight_path, "straight line")
+        .start(start)
+        .goal(goal)
+        .informed_ellipse(start, goal, 60.0);
+    let size = (400, 240);
+    let pixels = plot.to_rgb(size).unwrap();
+    assert_eq!(pixels.len(), 400 * 240 * 3);
+
+    // The legend is drawn over the upper left corner of the plot
+    let pixels_without_legend = plot.legend(false).to_rgb(size).unwrap();
+    assert_ne!(pixels, pixels_without_legend);
+
+    // Overlays are drawn on top of the environment alone
+    let env_pixels = PlotBuilder::new(&env).to_rgb(size).unwrap();
+    assert_eq!(env_pixels, EnvFigure { env: &env }.to_rgb(size).unwrap());
+    assert_ne!(env_pixels, pixels_without_legend);
+}
+
+/// Returns the width and height in pixels of the region drawn in `color`.
+fn get_pixel_extent(pixels: &[u8], size: (u32, u32), color: (u8, u8, u8)) -> (usize, usize) {
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (usize::MAX, 0, usize::MAX, 0);
+    for (k, pixel) in pixels.chunks(3).enumerate() {
+        if (pixel[0], pixel[1], pixel[2]) == color {
+            let (x, y) = (k % size.0 as usize, k / size.0 as usize);
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+        }
+    }
+    (max_x - min_x + 1, max_y - min_y + 1)
+}
+
+#[test]
+fn test_circle_scale() {
+    use plotters::style::{Color, Palette, Palette99};
+
+    let obstacles = vec![pp::env::Obstacle::CircleObstacle {
+        center: [50.0, 50.0],
+        radius: 20.0,
+    }];
+    let env = pp::env::Env::new([0.0, 0.0], [100.0, 100.0], obstacles);
+    let color = Palette99::pick(1).to_rgba().rgb();
+
+    // The square environment is limited by the 230 pixels high plotting area of a 400x300 image
+    let size = (400, 300);
+    let pixels = EnvFigure { env: &env }.to_rgb(size).unwrap();
+    let (width, height) = get_pixel_extent(&pixels, size, color);
+    assert!((width as i32 - height as i32).abs() <= 2);
+    assert!((88..=98).contains(&height));
+
+    // The circle scales with the image instead of keeping a fixed pixel radius
+    let size = (800, 600);
+    let pixels = EnvFigure { env: &env }.to_rgb(size).unwrap();
+    let (width, height) = get_pixel_extent(&pixels, size, color);
+    assert!((width as i32 - height as i32).abs() <= 2);
+    assert!((206..=218).contains(&height));
+}
+
+#[test]
+fn test_obstacle_mesh() {
+    use pp::env::Obstacle::*;
+
+    let env = pp::env::Env::new([0.0, 0.0, 0.0], [10.0, 10.0, 10.0], Vec::new());
+    let obstacles = [
+        RectObstacle {
+            center: [5.0, 5.0, 5.0],
+            size: [2.0, 4.0, 6.0],
+        },
+        CircleObstacle {
+            center: [5.0, 5.0, 5.0],
+            radius: 2.0,
+        },
+        CapsuleObstacle {
+            start: [3.0, 4.0, 5.0],
+            end: [7.0, 6.0, 5.0],
+            radius: 1.0,
+        },
+        UnionObstacle {
+            obstacles: vec![
+                CircleObstacle {
+                    center: [4.0, 5.0, 5.0],
+                    radius: 2.0,
+                },
+                CircleObstacle {
+                    center: [6.0, 5.0, 5.0],
+                    radius: 2.0,
+                },
+            ],
+        },
+    ];
+
+    for obstacle in obstacles.iter() {
+        let mesh = pp::plot::create_obstacle_mesh(&env, obstacle);
+        assert!(!mesh.faces.is_empty());
+
+        // All faces point away from the center of the convex obstacles, or from the center of the
+        // nearest sphere of the union
+        for (k, face) in mesh.faces.iter().enumerate() {
+            let normal = mesh.calc_face_normal(k);
+            let centroid: Vec<f32> = (0..3)
+                .map(|i| {
+                    face.iter()
+                        .map(|&index| mesh.vertices[index][i])
+                        .sum::<f32>()
+                        / 3.0
+                })
+                .collect();
+            let mut center = [5.0, 5.0, 5.0];
+            if let UnionObstacle { .. } = obstacle {
+                center[0] = if centroid[0] < 5.0 { 4.0 } else { 6.0 };
+            }
+            let outward: f32 = (0..3).map(|i| normal[i] * (centroid[i] - center[i])).sum();
+            assert!(outward > 0.0);
+        }
+    }
+
+    let mesh = pp::plot::create_obstacle_mesh(&env, &obstacles[0]);
+    assert_eq!(mesh.vertices.len(), 8);
+    assert_eq!(mesh.faces.len(), 12);
+
+    // Capsule vertices are at the radius from its segment
+    let mesh = pp::plot::create_obstacle_mesh(&env, &obstacles[2]);
+    let capsule = CapsuleObstacle {
+        start: [3.0, 4.0, 5.0],
+        end: [7.0, 6.0, 5.0],
+        radius: 0.999,
+    };
+    let larger_capsule = CapsuleObstacle {
+        start: [3.0, 4.0, 5.0],
+        end: [7.0, 6.0, 5.0],
+        radius: 1.001,
+    };
+    for vertex in mesh.vertices.iter() {
+        assert!(!capsule.is_inside(vertex));
+        assert!(larger_capsule.is_inside(vertex));
+    }
+}
+
+#[test]
+fn test_obj() {
+    let nodes = {
+        let mut root = pp::planner::Node::new([0.0, 0.0, 0.0]);
+        root.children.insert(1);
+        let mut child = pp::planner::Node::new([1.0, 0.0, 0.0]);
+        child.parent = Some(0);
+        vec![root, child]
+    };
+    let path = vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]];
+    let env = pp::env::Env::new(
+        [0.0, 0.0, 0.0],
+        [2.0, 2.0, 2.0],
+        vec![pp::env::Obstacle::RectObstacle {
+            center: [1.0, 1.0, 1.0],
+            size: [0.5, 0.5, 0.5],
+        }],
+    );
+
+    let meshes = [
+        pp::plot::create_env_mesh(&env),
+        pp::plot::create_tree_mesh(&nodes),
+        pp::plot::create_path_mesh(&path),
+    ];
+    let obj = pp::plot::to_obj_string(&meshes);
+    let lines: Vec<&str> = obj.lines().collect();
+    assert_eq!(
+        lines.iter().filter(|line| line.starts_with("o ")).count(),
+        3
+    );
+    assert_eq!(
+        lines.iter().filter(|line| line.starts_with("v ")).count(),
+        13
+    );
+    assert_eq!(
+        lines.iter().filter(|line| line.starts_with("f ")).count(),
+        12
+    );
+
+    // Indices are 1-based and continue across objects
+    assert!(lines.contains(&"l 9 10"));
+    assert!(lines.contains(&"l 11 12 13"));
+}
+
+#[test]
+fn test_plot_builder_3d() {
+    let env = pp::env::Env::new(
+        [0.0, 0.0, 0.0],
+        [10.0, 10.0, 10.0],
+        vec![pp::env::Obstacle::CircleObstacle {
+            center: [5.0, 5.0, 5.0],
+            radius: 2.0,
+        }],
+    );
+    let path = vec![[1.0, 1.0, 1.0], [9.0, 1.0, 9.0], [9.0, 9.0, 9.0]];
+
+    let size = (300, 200);
+    let plot = pp::plot::PlotBuilder3d::new(&env)
+        .path(&path, "path")
+        .start(path[0])
+        .goal(path[2]);
+    let pixels = plot.to_rgb(size).unwrap();
+    assert_eq!(pixels.len(), 300 * 200 * 3);
+    assert_ne!(pixels, plot.view(1.0, 0.5).to_rgb(size).unwrap());
+}
+
+/// Returns the number of frames drawn by the animation. Each frame starts with filling the
+/// background, and the SVG backend keeps every frame drawn into a string.
+fn count_frames(
+    env: &pp::env::Env<2>,
+    log: &pp::planner::TreeLog<2>,
+    options: &AnimationOptions,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    use plotters::prelude::{IntoDrawingArea, SVGBackend};
+
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, (400, 240)).into_drawing_area();
+        pp::plot::animate_path_on(&root, env, log, options)?;
+    }
+    Ok(svg
+        .matches("<rect x=\"0\" y=\"0\" width=\"400\" height=\"240\"")
+        .count())
+}
+
+#[test]
+fn test_animation_modes() {
+    pp::planner::set_seed(0);
+    let env = pp::env::create_example_2d_env();
+    let _env = env.clone();
+    let is_approved = Box::new(move |position: &[f32; 2]| !_env.is_inside_obstacle(position));
+
+    let (start, goal) = ([1.0, 1.0], [48.0, 25.0]);
+    let mut rrtstar =
+        pp::planner::RRTStar::new(start, goal, env.low, env.high, is_approved, 0.2, 2.0, 500);
+    rrtstar.enable_logging();
+    let path = rrtstar.plan();
+    assert!(!path.is_empty());
+
+    let num_snapshots = rrtstar.log.get_num_snapshots();
+    let options = AnimationOptions::default();
+    assert_eq!(
+        count_frames(&env, &rrtstar.log, &options).unwrap(),
+        num_snapshots
+    );
+
+    // Every 7th frame is drawn and the last frame is added
+    let options = AnimationOptions {
+        frame_step: 7,
+        ..AnimationOptions::default()
+    };
+    let expected_num_frames =
+        num_snapshots.div_ceil(7) + if (num_snapshots - 1) % 7 != 0 { 1 } else { 0 };
+    assert_eq!(
+        count_frames(&env, &rrtstar.log, &options).unwrap(),
+        expected_num_frames
+    );
+
+    // One frame per change of the cost of the best solution
+    let mut costs: Vec<f32> = Vec::new();
+    for snapshot in rrtstar.log.snapshots() {
+        if let Some(index) = snapshot.goal_node_index {
+            let cost = snapshot.nodes[index].cost;
+            if costs.last() != Some(&cost) {
+                costs.push(cost);
+            }
+        }
+    }
+    let options = AnimationOptions {
+        mode: AnimationMode::SolutionImprovement,
+        is_ellipse_shown: true,
+        ..AnimationOptions::default()
+    };
+    assert!(!costs.is_empty());
+    assert_eq!(
+        count_frames(&env, &rrtstar.log, &options).unwrap(),
+        costs.len()
+    );
+
+    // The agent moves by speed / fps per frame until it reaches the goal
+    let length = pp::path::Path::new(path).get_length();
+    let options = AnimationOptions {
+        mode: AnimationMode::PathExecution {
+            radius: 1.0,
+            speed: 10.0,
+        },
+        fps: 5.0,
+        ..AnimationOptions::default()
+    };
+    assert_eq!(
+        count_frames(&env, &rrtstar.log, &options).unwrap(),
+        (length / 2.0).ceil() as usize + 1
+    );
+
+    // An agent cannot move without a solution
+    let empty_log = pp::planner::TreeLog::new();
+    assert!(count_frames(&env, &empty_log, &options).is_err());
+
+    // A GIF needs a positive and finite frame rate
+    let gif_path = std::env::temp_dir().join("test_animation_modes.gif");
+    for fps in [0.0, -5.0, f32::NAN, f32::INFINITY] {
+        let options = AnimationOptions {
+            fps,
+            ..AnimationOptions::default()
+        };
+        assert!(pp::plot::animate_path_to(&env, &rrtstar.log, &gif_path, &options).is_err());
+    }
+}
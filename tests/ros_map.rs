@@ -0,0 +1,90 @@
+#![cfg(feature = "serde")]
+use path_planning::env::{load_ros_map, parse_ros_map_yaml, AbstractEnv, CellState, RosMapMode};
+use std::fs;
+
+#[test]
+fn test_parse_ros_map_yaml() {
+    let text = "image: map.pgm\n\
+                resolution: 0.050000\n\
+                origin: [-10.0, -5.0, 0.0]\n\
+                negate: 0\n\
+                occupied_thresh: 0.65 # comment\n\
+                free_thresh: 0.196\n";
+    let metadata = parse_ros_map_yaml(text).unwrap();
+
+    assert_eq!(metadata.image, "map.pgm");
+    assert_eq!(metadata.resolution, 0.05);
+    assert_eq!(metadata.origin, [-10.0, -5.0, 0.0]);
+    assert_eq!(metadata.occupied_thresh, 0.65);
+    assert!(!metadata.negate);
+    assert_eq!(metadata.mode, RosMapMode::Trinary);
+
+    assert!(parse_ros_map_yaml("image: map.pgm\n").is_err());
+
+    // Quoted values, comments after values and a flow sequence split across lines
+    let text = "image: \"my map.pgm\"  # saved map\n\
+                resolution: 0.1\n\
+                origin: [-10.0,\n  -5.0, 0.0]\n\
+                negate: true\n\
+                mode: scale\n";
+    let metadata = parse_ros_map_yaml(text).unwrap();
+
+    assert_eq!(metadata.image, "my map.pgm");
+    assert_eq!(metadata.origin, [-10.0, -5.0, 0.0]);
+    assert!(metadata.negate);
+    assert_eq!(metadata.mode, RosMapMode::Scale);
+}
+
+#[test]
+fn test_calc_occupancy() {
+    let mut metadata = parse_ros_map_yaml(
+        "image: map.pgm\nresolution: 0.05\norigin: [0.0, 0.0, 0.0]\n\
+         occupied_thresh: 0.75\nfree_thresh: 0.25\nmode: scale\n",
+    )
+    .unwrap();
+
+    assert_eq!(metadata.calc_occupancy(0, 255), 100);
+    assert_eq!(metadata.calc_occupancy(254, 255), 0);
+    // Occupancies between the thresholds are scaled linearly to 1 to 99
+    assert_eq!(metadata.calc_occupancy(191, 255), 1);
+    assert_eq!(metadata.calc_occupancy(128, 255), 49);
+    assert_eq!(metadata.calc_occupancy(64, 255), 98);
+    assert_eq!(metadata.calc_occupancy(128, 0), -1);
+
+    metadata.mode = RosMapMode::Trinary;
+    assert_eq!(metadata.calc_occupancy(128, 255), -1);
+
+    metadata.mode = RosMapMode::Raw;
+    assert_eq!(metadata.calc_occupancy(42, 255), 42);
+    assert_eq!(metadata.calc_occupancy(255, 255), -1);
+}
+
+#[test]
+fn test_load_ros_map() {
+    let directory = std::env::temp_dir().join("path_planning_test_load_ros_map");
+    fs::create_dir_all(&directory).unwrap();
+
+    // 3 x 2 image whose top row is occupied, free and unknown
+    let mut pgm = b"P5\n3 2\n255\n".to_vec();
+    pgm.extend_from_slice(&[0, 254, 205, 254, 254, 254]);
+    fs::write(directory.join("map.pgm"), pgm).unwrap();
+    fs::write(
+        directory.join("map.yaml"),
+        "image: map.pgm\nresolution: 0.5\norigin: [-1.0, 2.0, 0.0]\nnegate: 0\n\
+         occupied_thresh: 0.65\nfree_thresh: 0.196\n",
+    )
+    .unwrap();
+
+    let grid = load_ros_map(directory.join("map.yaml")).unwrap();
+    assert_eq!(grid.get_shape(), &[3, 2]);
+    assert_eq!(grid.get_low(), &[-1.0, 2.0]);
+    assert_eq!(grid.get_high(), &[0.5, 3.0]);
+    assert_eq!(grid.get_cell(&[0, 1]), CellState::Occupied);
+    assert_eq!(grid.get_cell(&[1, 1]), CellState::Free);
+    assert_eq!(grid.get_cell(&[2, 1]), CellState::Unknown);
+    assert_eq!(grid.get_cell(&[0, 0]), CellState::Free);
+    assert!(grid.is_inside_obstacle(&[-0.9, 2.9]));
+    assert!(!grid.is_inside_obstacle(&[-0.9, 2.1]));
+
+    fs::remove_dir_all(&directory).unwrap();
+}
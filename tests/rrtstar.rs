@@ -1,3 +1,4 @@
+use path_planning as pp;
 use path_planning::planner::RRTStar;
 
 #[test]
@@ -27,3 +28,47 @@ fn test_plan() {
     assert_eq!(path[0], start);
     assert_eq!(path[path.len() - 1], goal);
 }
+
+#[test]
+fn test_stats() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    pp::planner::set_seed(0);
+    let env = pp::env::create_example_2d_env();
+    let _env = env.clone();
+    let num_calls = Rc::new(Cell::new(0));
+    let _num_calls = num_calls.clone();
+    let is_approved = Box::new(move |position: &[f32; 2]| {
+        _num_calls.set(_num_calls.get() + 1);
+        !_env.is_inside_obstacle(position)
+    });
+
+    let mut rrtstar = RRTStar::new(
+        [1.0, 1.0],
+        [48.0, 25.0],
+        env.low,
+        env.high,
+        is_approved,
+        0.2,
+        2.0,
+        500,
+    );
+    rrtstar.edge_resolution = Some(0.5);
+    let path = rrtstar.plan();
+
+    // The start node, a node per approved sample and edge and the goal node if it was reached
+    let stats = &rrtstar.stats;
+    let num_goal_nodes = if path.len() >= 2 { 1 } else { 0 };
+    assert_eq!(stats.num_iterations, 500);
+    assert_eq!(stats.num_collision_checks, num_calls.get());
+    assert!(stats.num_collision_checks > stats.num_iterations);
+    assert_eq!(
+        rrtstar.nodes.len(),
+        1 + stats.num_iterations - stats.num_rejected_samples - stats.num_rejected_edges
+            + num_goal_nodes
+    );
+    assert!(stats.num_rejected_samples > 0);
+    assert!(stats.num_rewires > 0);
+    assert!(stats.nearest_neighbor_time > 0.0);
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "serde")]
+
+use path_planning as pp;
+use pp::scenario::{PlannerKind, Scenario};
+
+fn assert_same_as_example_env(env: &pp::env::Env<2>) {
+    let example_env = pp::env::create_example_2d_env();
+    assert_eq!(env.low, example_env.low);
+    assert_eq!(env.high, example_env.high);
+    assert_eq!(env.get_obstacles().len(), example_env.get_obstacles().len());
+    for x in 0..50 {
+        for y in 0..30 {
+            let position = [x as f32 + 0.5, y as f32 + 0.5];
+            assert_eq!(
+                env.is_inside_obstacle(&position),
+                example_env.is_inside_obstacle(&position)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_load_toml() {
+    let scenario: Scenario<2> = Scenario::load("examples/scenarios/example_2d.toml").unwrap();
+    assert_eq!(scenario.planner.kind, PlannerKind::RRTStar);
+    assert_eq!(scenario.queries.len(), 1);
+    assert_eq!(scenario.queries[0].goal, [48.0, 25.0]);
+    assert_same_as_example_env(&scenario.create_env());
+}
+
+#[test]
+fn test_load_json() {
+    let scenario: Scenario<2> = Scenario::load("examples/scenarios/example_2d.json").unwrap();
+    assert_eq!(scenario.planner.kind, PlannerKind::InformedRRTStar);
+    assert_eq!(scenario.planner.max_iter, 1000);
+    assert_eq!(scenario.planner.step_size, 2.0);
+    assert_eq!(scenario.queries.len(), 2);
+    assert_same_as_example_env(&scenario.create_env());
+
+    let toml = scenario.to_toml_string().unwrap();
+    let deserialized_scenario: Scenario<2> = Scenario::from_toml_str(&toml).unwrap();
+    assert_eq!(deserialized_scenario.queries[1].start, [48.0, 1.0]);
+    assert_same_as_example_env(&deserialized_scenario.create_env());
+}
+
+#[test]
+fn test_invalid_scenario() {
+    let result: Result<Scenario<3>, _> = Scenario::load("examples/scenarios/example_2d.json");
+    assert!(result.is_err());
+    let result: Result<Scenario<2>, _> = Scenario::load("Cargo.toml");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_create_planners() {
+    let mut scenario: Scenario<2> = Scenario::load("examples/scenarios/example_2d.toml").unwrap();
+    scenario.planner.kind = PlannerKind::RRT;
+    let env = scenario.create_env();
+
+    let mut planners = scenario.create_planners(&env);
+    assert_eq!(planners.len(), 1);
+    let path = planners[0].plan();
+    assert!(path.len() >= 2);
+    assert_eq!(path[0], [1.0, 1.0]);
+    assert_eq!(path[path.len() - 1], [48.0, 25.0]);
+    for position in path.iter() {
+        assert!(!env.is_inside_obstacle(position));
+    }
+}
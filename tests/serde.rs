@@ -0,0 +1,91 @@
+#![cfg(feature = "serde")]
+
+use path_planning as pp;
+use pp::env::{create_2d_rotation, Env, Obstacle};
+
+fn create_example_env() -> Env<2> {
+    let mut env = pp::env::create_example_2d_env();
+    env.add_obstacle(Obstacle::OrientedRectObstacle {
+        center: [10.0, 25.0],
+        size: [4.0, 1.0],
+        rotation: create_2d_rotation(0.3),
+    });
+    env.add_obstacle(Obstacle::DifferenceObstacle {
+        base: Box::new(Obstacle::RectObstacle {
+            center: [45.0, 5.0],
+            size: [6.0, 6.0],
+        }),
+        subtracted: vec![Obstacle::CircleObstacle {
+            center: [45.0, 5.0],
+            radius: 2.0,
+        }],
+    });
+    env.add_obstacle(Obstacle::PolygonObstacle {
+        vertices: vec![[1.0, 1.0], [4.0, 1.0], [2.0, 3.0]],
+    });
+    env
+}
+
+fn assert_same_env(env: &Env<2>, other: &Env<2>) {
+    assert_eq!(env.low, other.low);
+    assert_eq!(env.high, other.high);
+    assert_eq!(env.get_obstacles().len(), other.get_obstacles().len());
+    for x in 0..50 {
+        for y in 0..30 {
+            let position = [x as f32 + 0.5, y as f32 + 0.5];
+            assert_eq!(
+                env.is_inside_obstacle(&position),
+                other.is_inside_obstacle(&position)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_env_json() {
+    let env = create_example_env();
+    let json = serde_json::to_string(&env).unwrap();
+    let deserialized_env: Env<2> = serde_json::from_str(&json).unwrap();
+    assert_same_env(&env, &deserialized_env);
+}
+
+#[test]
+fn test_env_yaml() {
+    let env = create_example_env();
+    let yaml = serde_yaml::to_string(&env).unwrap();
+    let deserialized_env: Env<2> = serde_yaml::from_str(&yaml).unwrap();
+    assert_same_env(&env, &deserialized_env);
+}
+
+#[test]
+fn test_obstacle_format() {
+    let obstacle: Obstacle<3> = serde_json::from_str(
+        r#"{"type": "CircleObstacle", "center": [1.0, 2.0, 3.0], "radius": 0.5}"#,
+    )
+    .unwrap();
+    assert!(obstacle.is_inside(&[1.0, 2.0, 3.4]));
+
+    let result: Result<Obstacle<3>, _> =
+        serde_json::from_str(r#"{"type": "CircleObstacle", "center": [1.0, 2.0], "radius": 0.5}"#);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_node_and_path() {
+    let mut node = pp::planner::Node::new([1.0, 2.0, 3.0]);
+    node.parent = Some(4);
+    node.children.insert(7);
+    node.cost = 2.5;
+
+    let json = serde_json::to_string(&node).unwrap();
+    let deserialized_node: pp::planner::Node<3> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized_node.position, node.position);
+    assert_eq!(deserialized_node.parent, node.parent);
+    assert_eq!(deserialized_node.children, node.children);
+    assert_eq!(deserialized_node.cost, node.cost);
+
+    let path = pp::path::Path::new(vec![[0.0, 0.0], [1.0, 2.0], [3.0, 3.0]]);
+    let yaml = serde_yaml::to_string(&path).unwrap();
+    let deserialized_path: pp::path::Path<2> = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(deserialized_path.waypoints, path.waypoints);
+}
@@ -0,0 +1,57 @@
+use path_planning::env::Obstacle;
+use path_planning::path::PathSmoother;
+
+fn create_example_2d_smoother() -> PathSmoother<2> {
+    let obstacle = Obstacle::RectObstacle {
+        center: [5.0, 5.0],
+        size: [2.0, 6.0],
+    };
+    let is_approved = Box::new(move |position: &[f32; 2]| !obstacle.is_inside(position));
+    PathSmoother::new(is_approved, 0.1)
+}
+
+#[test]
+fn test_is_segment_approved() {
+    let smoother = create_example_2d_smoother();
+    assert!(smoother.is_segment_approved(&[0.0, 0.0], &[10.0, 0.0]));
+    assert!(!smoother.is_segment_approved(&[0.0, 5.0], &[10.0, 5.0]));
+}
+
+#[test]
+fn test_prune() {
+    let smoother = create_example_2d_smoother();
+    let path = vec![
+        [0.0, 5.0],
+        [0.0, 2.5],
+        [0.0, 0.0],
+        [5.0, 0.0],
+        [10.0, 0.0],
+        [10.0, 5.0],
+    ];
+
+    let pruned_path = smoother.prune(&path);
+    assert_eq!(pruned_path, vec![[0.0, 5.0], [5.0, 0.0], [10.0, 5.0]]);
+}
+
+#[test]
+fn test_shortcut() {
+    let smoother = create_example_2d_smoother();
+    let path = vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0], [3.0, 1.0], [4.0, 0.0]];
+
+    let shortcut_path = smoother.shortcut(&path, 100);
+    assert_eq!(shortcut_path, vec![[0.0, 0.0], [4.0, 0.0]]);
+}
+
+#[test]
+fn test_relax() {
+    let smoother = create_example_2d_smoother();
+    let path = vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0]];
+
+    let relaxed_path = smoother.relax(&path, 100, 0.5);
+    assert_eq!(relaxed_path[0], path[0]);
+    assert_eq!(relaxed_path[2], path[2]);
+    assert!(relaxed_path[1][1].abs() < 1E-3);
+    for k in 0..relaxed_path.len() - 1 {
+        assert!(smoother.is_segment_approved(&relaxed_path[k], &relaxed_path[k + 1]));
+    }
+}
@@ -0,0 +1,132 @@
+use path_planning::env::{Env, Obstacle};
+use path_planning::trajectory::*;
+
+#[test]
+fn test_cubic_spline() {
+    let waypoints = vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0], [3.0, 1.0]];
+    let spline = CubicSpline::new(&waypoints);
+
+    for (k, waypoint) in waypoints.iter().enumerate() {
+        let position = spline.evaluate(spline.knots[k]);
+        assert!((position[0] - waypoint[0]).abs() < 1E-4);
+        assert!((position[1] - waypoint[1]).abs() < 1E-4);
+    }
+}
+
+#[test]
+fn test_b_spline() {
+    let control_points = vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0], [3.0, 1.0], [4.0, 0.0]];
+    let spline = BSpline::new(&control_points, 3);
+
+    assert_eq!(spline.evaluate(0.0), [0.0, 0.0]);
+    assert_eq!(spline.evaluate(1.0), [4.0, 0.0]);
+    for position in spline.sample(50).iter() {
+        assert!(0.0 <= position[0] && position[0] <= 4.0);
+        assert!(0.0 <= position[1] && position[1] <= 1.0);
+    }
+}
+
+#[test]
+fn test_is_curve_collision_free() {
+    let obstacles = vec![Obstacle::CircleObstacle {
+        center: [2.0, 0.0],
+        radius: 0.5,
+    }];
+    let env = Env::new([-1.0, -1.0], [5.0, 5.0], obstacles);
+
+    let spline = CubicSpline::new(&[[0.0, 0.0], [4.0, 0.0]]);
+    assert!(!is_curve_collision_free(&spline, &env, 0.1));
+
+    let spline = CubicSpline::new(&[[0.0, 0.0], [2.0, 2.0], [4.0, 0.0]]);
+    assert!(is_curve_collision_free(&spline, &env, 0.1));
+}
+
+#[test]
+fn test_parameterize_trapezoidal() {
+    let points: Vec<[f32; 2]> = (0..=100).map(|k| [k as f32 * 0.1, 0.0]).collect();
+    let trajectory = parameterize_trapezoidal(&points, [1.0, 2.0], [0.5, 1.0]).unwrap();
+
+    // Accelerate for 2 s over 1 m, cruise for 8 s over 8 m and decelerate for 2 s over 1 m
+    assert!((trajectory.get_duration() - 12.0).abs() < 0.1);
+    assert_eq!(trajectory.velocities[0], [0.0, 0.0]);
+    assert_eq!(trajectory.velocities[100], [0.0, 0.0]);
+    assert!((trajectory.velocities[50][0] - 1.0).abs() < 1E-4);
+    assert_eq!(
+        trajectory.get_position(trajectory.get_duration()),
+        [10.0, 0.0]
+    );
+}
+
+#[test]
+fn test_parameterize_time_optimal() {
+    let spline = CubicSpline::new(&[[0.0, 0.0], [5.0, 5.0], [10.0, 0.0]]);
+    let points = spline.sample(500);
+    let max_velocity = [1.0, 1.0];
+    let max_acceleration = [1.0, 1.0];
+    let trajectory = parameterize_time_optimal(&points, max_velocity, max_acceleration).unwrap();
+
+    assert!(trajectory.get_duration() > 0.0);
+    for velocity in trajectory.velocities.iter() {
+        for i in 0..2 {
+            assert!(velocity[i].abs() <= max_velocity[i] + 1E-4);
+        }
+    }
+    for k in 1..trajectory.times.len() {
+        assert!(trajectory.times[k - 1] <= trajectory.times[k]);
+    }
+}
+
+#[test]
+fn test_parameterize_trapezoidal_sparse() {
+    let max_acceleration = [0.5, 1.0];
+    for goal in [[10.0, 0.0], [1.0, 0.0]] {
+        let trajectory =
+            parameterize_trapezoidal(&[[0.0, 0.0], goal], [1.0, 2.0], max_acceleration).unwrap();
+
+        assert!(trajectory.get_duration() > 0.0);
+        assert_eq!(trajectory.get_position(trajectory.get_duration()), goal);
+        for k in 1..trajectory.times.len() {
+            let dt = trajectory.times[k] - trajectory.times[k - 1];
+            assert!(dt > 0.0);
+            let acceleration = (trajectory.velocities[k][0] - trajectory.velocities[k - 1][0]) / dt;
+            assert!(acceleration.abs() <= max_acceleration[0] + 1E-4);
+        }
+    }
+
+    // Accelerate for 2 s over 1 m, cruise for 8 s over 8 m and decelerate for 2 s over 1 m
+    let trajectory =
+        parameterize_trapezoidal(&[[0.0, 0.0], [10.0, 0.0]], [1.0, 2.0], [0.5, 1.0]).unwrap();
+    assert!((trajectory.get_duration() - 12.0).abs() < 1E-3);
+}
+
+#[test]
+fn test_parameterize_time_optimal_sparse() {
+    let max_acceleration = [0.5, 1.0];
+    let points = [[0.0, 0.0], [0.0, 0.0], [4.0, 0.0]];
+    let trajectory = parameterize_time_optimal(&points, [1.0, 2.0], max_acceleration).unwrap();
+
+    assert!(trajectory.get_duration() > 0.0);
+    assert_eq!(
+        trajectory.get_position(trajectory.get_duration()),
+        [4.0, 0.0]
+    );
+    for k in 1..trajectory.times.len() {
+        let dt = trajectory.times[k] - trajectory.times[k - 1];
+        assert!(dt > 0.0);
+        let acceleration = (trajectory.velocities[k][0] - trajectory.velocities[k - 1][0]) / dt;
+        assert!(acceleration.abs() <= max_acceleration[0] + 1E-4);
+    }
+}
+
+#[test]
+fn test_parameterize_invalid_limits() {
+    let points = [[0.0, 0.0], [1.0, 0.0]];
+    for (max_velocity, max_acceleration) in [
+        ([1.0, 1.0], [0.0, 1.0]),
+        ([1.0, -1.0], [1.0, 1.0]),
+        ([1.0, 1.0], [f32::NAN, 1.0]),
+    ] {
+        assert!(parameterize_trapezoidal(&points, max_velocity, max_acceleration).is_err());
+        assert!(parameterize_time_optimal(&points, max_velocity, max_acceleration).is_err());
+    }
+}
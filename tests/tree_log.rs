@@ -0,0 +1,109 @@
+use path_planning as pp;
+use pp::planner::{Node, RRTStar, TreeEvent, RRT};
+
+fn assert_same_nodes(nodes: &[Node<2>], other_nodes: &[Node<2>]) {
+    assert_eq!(nodes.len(), other_nodes.len());
+    for (node, other_node) in nodes.iter().zip(other_nodes.iter()) {
+        assert_eq!(node.position, other_node.position);
+        assert_eq!(node.parent, other_node.parent);
+        assert_eq!(node.children, other_node.children);
+        assert!((node.cost - other_node.cost).abs() < 1E-3);
+    }
+}
+
+#[test]
+fn test_rrtstar_log() {
+    let env = pp::env::create_example_2d_env();
+    let _env = env.clone();
+    let is_approved = Box::new(move |position: &[f32; 2]| !_env.is_inside_obstacle(position));
+
+    let mut rrtstar = RRTStar::new(
+        [1.0, 1.0],
+        [48.0, 25.0],
+        env.low,
+        env.high,
+        is_approved,
+        0.2,
+        2.0,
+        1000,
+    );
+    rrtstar.enable_logging();
+    let path = rrtstar.plan();
+
+    let log = &rrtstar.log;
+    let num_snapshots = log.get_num_snapshots();
+    assert_eq!(
+        num_snapshots,
+        rrtstar.stats.num_iterations
+            - rrtstar.stats.num_rejected_samples
+            - rrtstar.stats.num_rejected_edges
+    );
+    assert!(log
+        .events
+        .iter()
+        .any(|event| matches!(event, TreeEvent::ParentChanged { .. })));
+
+    let last_snapshot = log.snapshot(num_snapshots - 1);
+    assert_same_nodes(&last_snapshot.nodes, &rrtstar.nodes);
+    assert_eq!(last_snapshot.extract_path(), path);
+
+    let snapshots: Vec<_> = log.snapshots().collect();
+    assert_eq!(snapshots.len(), num_snapshots);
+    for k in [0, num_snapshots / 2, num_snapshots - 1] {
+        assert_same_nodes(&snapshots[k].nodes, &log.snapshot(k).nodes);
+    }
+    assert_eq!(snapshots[0].nodes.len(), 2);
+    assert!(snapshots[0].goal_node_index.is_none());
+
+    let mut num_visited = 0;
+    log.replay(|k, snapshot| {
+        assert_eq!(k, num_visited);
+        assert_same_nodes(&snapshot.nodes, &snapshots[k].nodes);
+        num_visited += 1;
+        Ok::<(), ()>(())
+    })
+    .unwrap();
+    assert_eq!(num_visited, num_snapshots);
+    assert_eq!(
+        log.replay(|k, _| if k == 3 { Err(k) } else { Ok(()) }),
+        Err(3)
+    );
+}
+
+#[test]
+fn test_rrt_log() {
+    let is_approved = Box::new(|_position: &[f32; 2]| true);
+    let mut rrt = RRT::new(
+        [1.0, 1.0],
+        [48.0, 25.0],
+        [0.0, 0.0],
+        [50.0, 30.0],
+        is_approved,
+        0.2,
+        2.0,
+        2000,
+    );
+    rrt.enable_logging();
+    let path = rrt.plan();
+
+    let last_snapshot = rrt.log.snapshots().last().unwrap();
+    assert_eq!(last_snapshot.nodes.len(), rrt.nodes.len());
+    assert_eq!(last_snapshot.extract_path(), path);
+}
+
+#[test]
+fn test_logging_disabled() {
+    let is_approved = Box::new(|_position: &[f32; 2]| true);
+    let mut rrtstar = RRTStar::new(
+        [1.0, 1.0],
+        [48.0, 25.0],
+        [0.0, 0.0],
+        [50.0, 30.0],
+        is_approved,
+        0.2,
+        2.0,
+        100,
+    );
+    rrtstar.plan();
+    assert!(rrtstar.log.events.is_empty());
+}